@@ -79,7 +79,7 @@ struct ImpressionOrder {
 impl ImpressionOrder {
     fn middle_page(&self) -> Option<u32> {
         let length = self.pages.len();
-        if length % 2 == 0 {
+        if length.is_multiple_of(2) {
             None
         } else {
             Some(self.pages[length / 2])
@@ -1,8 +1,10 @@
 advent_of_code::solution!(6);
 
+use advent_of_code::collections::{BitGrid, DirectionalBitGrid};
+use advent_of_code::cycle;
 use std::iter::successors;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Dir {
     Up,
     Down,
@@ -11,6 +13,12 @@ enum Dir {
 }
 
 impl Dir {
+    const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
     fn to_vec(self) -> (i8, i8) {
         match self {
             Dir::Up => (0, -1),
@@ -59,7 +67,7 @@ impl Pos {
     }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq)]
 struct Guard {
     pos: Pos,
     facing: Dir,
@@ -78,11 +86,10 @@ impl std::fmt::Debug for Guard {
 
 #[derive(Clone)]
 struct State {
-    walls: Vec<Pos>,
-    walked: Vec<Guard>,
+    walls: BitGrid,
+    walked: DirectionalBitGrid,
     guard: Guard,
     map_size: Pos,
-    is_looping: bool,
 }
 
 impl std::fmt::Debug for State {
@@ -91,20 +98,24 @@ impl std::fmt::Debug for State {
         for y in 0..self.map_size.y {
             for x in 0..self.map_size.x {
                 let pos = Pos { x, y };
-                if self.walls.contains(&pos) {
+                if self.walls.contains(x, y) {
                     write!(f, "#")?;
                     continue;
                 }
-                // screw the cpu
-                let match_walk = self
-                    .walked
-                    .clone()
+                let facings = Dir::ALL
                     .into_iter()
-                    .filter(|g| g.pos == pos)
+                    .filter(|d| self.walked.contains(x, y, d.index()))
                     .collect::<Vec<_>>();
-                match match_walk.len() {
-                    0 => write!(f, ".")?,
-                    1 => write!(f, "{:?}", match_walk[0])?,
+                match facings.as_slice() {
+                    [] => write!(f, ".")?,
+                    [facing] => write!(
+                        f,
+                        "{:?}",
+                        Guard {
+                            pos,
+                            facing: *facing
+                        }
+                    )?,
                     _ => write!(f, "*")?,
                 }
             }
@@ -116,13 +127,13 @@ impl std::fmt::Debug for State {
 
 impl State {
     fn step(self) -> Option<Self> {
-        if self.is_last_step() || self.is_looping {
+        if self.is_last_step() {
             return None;
         }
         let guard_walk_direction = self.guard.facing.to_vec();
         let guard_walk = successors(Some(self.guard.pos), |pos| {
             if let Some(new_pos) = pos.move_towards(guard_walk_direction, &self.map_size) {
-                if !self.walls.contains(&new_pos) {
+                if !self.walls.contains(new_pos.x, new_pos.y) {
                     return Some(new_pos);
                 }
             }
@@ -133,18 +144,15 @@ impl State {
             pos: *guard_walk.last().unwrap(),
             facing: self.guard.facing.rotate90(),
         };
-        let is_looping = self.walked.contains(&guard);
 
-        let mut walked: Vec<Guard> = self.walked.clone();
-        walked.extend(guard_walk.iter().map(|p| Guard {
-            pos: *p,
-            facing: self.guard.facing,
-        }));
+        let mut walked = self.walked.clone();
+        guard_walk.iter().for_each(|p| {
+            walked.insert(p.x, p.y, self.guard.facing.index());
+        });
 
         Some(Self {
             guard,
             walked,
-            is_looping,
             ..self
         })
     }
@@ -159,9 +167,30 @@ impl State {
             .unwrap()
     }
 
+    // where the guard ends up after walking to the next wall and turning.
+    fn next_turn(&self, guard: &Guard) -> Option<Guard> {
+        if guard.pos.is_at_limit(&self.map_size) {
+            return None;
+        }
+        let direction = guard.facing.to_vec();
+        let pos = successors(Some(guard.pos), |pos| {
+            pos.move_towards(direction, &self.map_size)
+                .filter(|new_pos| !self.walls.contains(new_pos.x, new_pos.y))
+        })
+        .last()?;
+        Some(Guard {
+            pos,
+            facing: guard.facing.rotate90(),
+        })
+    }
+
+    fn is_looping(&self) -> bool {
+        cycle::brent(self.guard.clone(), |g| self.next_turn(g)).is_some()
+    }
+
     fn add_wall(&self, wall: Pos) -> Self {
         let mut walls = self.walls.clone();
-        walls.push(wall);
+        walls.insert(wall.x, wall.y);
         Self {
             walls,
             ..self.clone()
@@ -169,10 +198,11 @@ impl State {
     }
 
     fn get_visited_pos(&self) -> Vec<Pos> {
-        let mut visited: Vec<Pos> = self.walked.iter().map(|g| g.pos).collect();
-        visited.sort();
-        visited.dedup();
-        visited
+        self.walked
+            .cells()
+            .iter()
+            .map(|(x, y)| Pos::init(x, y))
+            .collect()
     }
 }
 
@@ -216,12 +246,17 @@ fn parse_input(input: &str) -> State {
         pos: data.1.unwrap(),
         facing: Dir::Up,
     };
+    let mut walls = BitGrid::new(size.x, size.y);
+    data.0.iter().for_each(|w| {
+        walls.insert(w.x, w.y);
+    });
+    let mut walked = DirectionalBitGrid::new(size.x, size.y);
+    walked.insert(guard.pos.x, guard.pos.y, guard.facing.index());
     State {
-        walls: data.0,
-        guard: guard.clone(),
-        walked: vec![guard],
+        walls,
+        guard,
+        walked,
         map_size: size,
-        is_looping: false,
     }
 }
 
@@ -239,7 +274,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         .get_last_state()
         .get_visited_pos()
         .into_iter()
-        .filter(|p| *p != start.guard.pos && start.add_wall(*p).get_last_state().is_looping)
+        .filter(|p| *p != start.guard.pos && start.add_wall(*p).is_looping())
         .count()
         .into()
 }
@@ -1,5 +1,6 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, part_one: [part_one_naive], part_two: [part_two_naive]);
 
+use advent_of_code::math::number_theory::concat_digits;
 use itertools::{repeat_n, Itertools};
 
 #[derive(Debug)]
@@ -48,8 +49,8 @@ impl Equ {
 
     fn concat(&mut self, lhs: u64) -> u64 {
         match self {
-            Equ::Nb(x) => return format!("{}{}", lhs, x).parse::<u64>().unwrap(),
-            Equ::Op(x, _, _) => *x = format!("{}{}", lhs, x).parse::<u64>().unwrap(),
+            Equ::Nb(x) => return concat_digits(lhs, *x),
+            Equ::Op(x, _, _) => *x = concat_digits(lhs, *x),
         }
         self.solve()
     }
@@ -130,9 +131,47 @@ pub fn part_two(input: &str) -> Option<u64> {
         .into()
 }
 
+/// Sum of the results that some combination of `operators`, evaluated left to right, produces.
+fn sum_solvable(input: &str, operators: &[fn(u64, u64) -> u64]) -> Option<u64> {
+    let mut sum = 0;
+    for line in input.lines() {
+        let (result, numbers) = line.split_once(": ")?;
+        let result: u64 = result.parse().ok()?;
+        let numbers: Vec<u64> = numbers
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        let solvable = repeat_n(operators.iter(), numbers.len() - 1)
+            .multi_cartesian_product()
+            .any(|ops| {
+                ops.iter()
+                    .zip(&numbers[1..])
+                    .fold(numbers[0], |acc, (op, &n)| op(acc, n))
+                    == result
+            });
+        if solvable {
+            sum += result;
+        }
+    }
+    Some(sum)
+}
+
+/// Tries every combination of `+` and `*`.
+pub fn part_one_naive(input: &str) -> Option<u64> {
+    sum_solvable(input, &[|a, b| a + b, |a, b| a * b])
+}
+
+/// Tries every combination of `+`, `*` and `||`.
+pub fn part_two_naive(input: &str) -> Option<u64> {
+    sum_solvable(input, &[|a, b| a + b, |a, b| a * b, concat_digits])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{self, generate};
+    use advent_of_code::variant;
 
     #[test]
     fn test_part_one() {
@@ -145,4 +184,22 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(11387));
     }
+
+    #[test]
+    fn part_one_agrees_with_brute_force() {
+        property::assert_agree(
+            |rng, size| generate::equations(rng, size, false),
+            &variant!(part_one_naive),
+            &variant!(part_one),
+        );
+    }
+
+    #[test]
+    fn part_two_agrees_with_brute_force() {
+        property::assert_agree(
+            |rng, size| generate::equations(rng, size, true),
+            &variant!(part_two_naive),
+            &variant!(part_two),
+        );
+    }
 }
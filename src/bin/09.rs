@@ -1,4 +1,4 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, part_one: [part_one_naive], part_two: [part_two_naive]);
 
 use std::collections::VecDeque;
 
@@ -183,9 +183,68 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(mem.compute_checksum())
 }
 
+/// Every block of the disk, with the id of the file it belongs to.
+fn expand(input: &str) -> Vec<Option<usize>> {
+    input
+        .chars()
+        .flat_map(|c| c.to_digit(10))
+        .enumerate()
+        .flat_map(|(i, size)| {
+            let file_id = (i % 2 == 0).then_some(i / 2);
+            std::iter::repeat_n(file_id, size as usize)
+        })
+        .collect()
+}
+
+fn checksum(blocks: &[Option<usize>]) -> u64 {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (i * id.unwrap_or(0)) as u64)
+        .sum()
+}
+
+/// Moves blocks one at a time from the end to the leftmost free block, as the puzzle describes.
+pub fn part_one_naive(input: &str) -> Option<u64> {
+    let mut blocks = expand(input);
+    while let Some(free) = blocks.iter().position(Option::is_none) {
+        match blocks.pop()? {
+            Some(id) => blocks[free] = Some(id),
+            None => continue,
+        }
+    }
+    Some(checksum(&blocks))
+}
+
+/// Moves whole files in decreasing id order to the leftmost free span that fits them.
+pub fn part_two_naive(input: &str) -> Option<u64> {
+    let mut blocks = expand(input);
+    let last_id = blocks.iter().flatten().max().copied()?;
+    for id in (0..=last_id).rev() {
+        // files of size 0 have no blocks to move.
+        let Some(start) = blocks.iter().position(|&b| b == Some(id)) else {
+            continue;
+        };
+        let size = blocks[start..]
+            .iter()
+            .take_while(|&&b| b == Some(id))
+            .count();
+        let free = blocks[..start]
+            .windows(size)
+            .position(|w| w.iter().all(Option::is_none));
+        if let Some(free) = free {
+            blocks[free..free + size].fill(Some(id));
+            blocks[start..start + size].fill(None);
+        }
+    }
+    Some(checksum(&blocks))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{self, generate};
+    use advent_of_code::variant;
 
     #[test]
     fn test_part_one() {
@@ -198,4 +257,28 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn skips_empty_files() {
+        assert_eq!(part_two_naive("12031"), Some(2));
+        assert_eq!(part_two_naive("12031"), part_two("12031"));
+    }
+
+    #[test]
+    fn part_one_agrees_with_block_by_block() {
+        property::assert_agree(
+            generate::disk_map,
+            &variant!(part_one_naive),
+            &variant!(part_one),
+        );
+    }
+
+    #[test]
+    fn part_two_agrees_with_block_by_block() {
+        property::assert_agree(
+            generate::disk_map,
+            &variant!(part_two_naive),
+            &variant!(part_two),
+        );
+    }
 }
@@ -1,62 +1,69 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, part_one: [part_one_naive]);
 
-use memoize::memoize;
+use advent_of_code::math::number_theory::{digit_count, split_digits};
+use advent_of_code::memo::Memo;
+use std::iter::successors;
 
-#[memoize]
-fn get_child_stones_count(stone: u64, depth: u8) -> usize {
+type StonesMemo = Memo<(u64, u8), usize>;
+
+fn get_child_stones_count(memo: &mut StonesMemo, stone: u64, depth: u8) -> usize {
     if depth == 0 {
         return 1;
     }
-    let digits = stone.checked_ilog10().unwrap_or(0) + 1;
-    match (stone, digits) {
-        (0, _) => get_child_stones_count(1, depth - 1),
-        (n, digits) if digits % 2 == 0 && digits > 0 => {
-            let div = 10u64.pow(digits / 2);
-            get_child_stones_count(n / div, depth - 1) + get_child_stones_count(n % div, depth - 1)
+    memo.get_or_insert_with((stone, depth), |memo| match (stone, digit_count(stone)) {
+        (0, _) => get_child_stones_count(memo, 1, depth - 1),
+        (n, digits) if digits % 2 == 0 => {
+            let (left, right) = split_digits(n, digits / 2);
+            get_child_stones_count(memo, left, depth - 1)
+                + get_child_stones_count(memo, right, depth - 1)
         }
-        _ => get_child_stones_count(stone * 2024, depth - 1),
-    }
+        _ => get_child_stones_count(memo, stone * 2024, depth - 1),
+    })
 }
 
-// fn update_stones(stones: &Vec<u64>) -> Vec<u64> {
-//     stones.into_iter().flat_map(|st|
-//         match (st, st.checked_ilog10().unwrap_or(0) + 1) {
-//             (0, _) => vec![1],
-//             (n, digits) if digits % 2 == 0 && digits > 0 => {
-//                 let div = 10u64.pow(digits / 2);
-//                 vec![
-//                     n / div,
-//                     n % div,
-//                 ]
-//             },
-//             _ => vec![st * 2024],
-//         }
-//     ).collect()
-// }
+/// Applies the rules to every stone, the way the puzzle describes them.
+fn update_stones(stones: &[u64]) -> Vec<u64> {
+    stones
+        .iter()
+        .flat_map(|&stone| match (stone, digit_count(stone)) {
+            (0, _) => vec![1],
+            (n, digits) if digits % 2 == 0 => {
+                let (left, right) = split_digits(n, digits / 2);
+                vec![left, right]
+            }
+            _ => vec![stone * 2024],
+        })
+        .collect()
+}
 
 fn parse_input(input: &str) -> Result<Vec<u64>, std::num::ParseIntError> {
     input.split_whitespace().map(|c| c.parse::<u64>()).collect()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
+    let mut memo = StonesMemo::new();
     parse_input(input)
         .ok()?
         .into_iter()
-        .map(|st| get_child_stones_count(st, 25))
+        .map(|st| get_child_stones_count(&mut memo, st, 25))
         .sum::<usize>()
         .into()
-    // successors(Some(stones), |st| Some(update_stones(st)))
-    //     .nth(25)
-    //     .unwrap()
-    //     .len()
-    //     .into()
+}
+
+/// Simulates every stone, only feasible for part one.
+pub fn part_one_naive(input: &str) -> Option<usize> {
+    let stones = parse_input(input).ok()?;
+    successors(Some(stones), |stones| Some(update_stones(stones)))
+        .nth(25)
+        .map(|stones| stones.len())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
+    let mut memo = StonesMemo::new();
     parse_input(input)
         .ok()?
         .into_iter()
-        .map(|st| get_child_stones_count(st, 75))
+        .map(|st| get_child_stones_count(&mut memo, st, 75))
         .sum::<usize>()
         .into()
 }
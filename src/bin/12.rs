@@ -1,6 +1,6 @@
 advent_of_code::solution!(12);
 
-use std::collections::HashMap;
+use advent_of_code::collections::FastHashMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Pos {
@@ -149,7 +149,7 @@ impl Region {
             })
             .collect();
 
-        let mut neighbors_map: HashMap<Pos, Vec<Pos>> = HashMap::new();
+        let mut neighbors_map: FastHashMap<Pos, Vec<Pos>> = FastHashMap::default();
         let mut outward_angles = 0;
         data.iter().for_each(|(p, ps)| {
             let l = match ps.len() {
@@ -1,17 +1,18 @@
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, part_one: [part_one_naive]);
 
+use advent_of_code::math::linear;
 use std::num::ParseIntError;
 
 #[derive(Debug)]
 struct Pos {
-    x: i32,
-    y: i32,
+    x: i64,
+    y: i64,
 }
 
 impl Pos {
     fn from_str(x: &str, y: &str) -> Result<Pos, ParseIntError> {
-        let x = x.strip_suffix(",").unwrap_or(x)[2..].parse::<i32>()?;
-        let y = y.strip_suffix(",").unwrap_or(y)[2..].parse::<i32>()?;
+        let x = x.strip_suffix(",").unwrap_or(x)[2..].parse::<i64>()?;
+        let y = y.strip_suffix(",").unwrap_or(y)[2..].parse::<i64>()?;
         Ok(Pos { x, y })
     }
 }
@@ -26,53 +27,21 @@ struct ClawMachine {
 impl ClawMachine {
     // 8400 = 94a + 22b
     // 5400 = 34a + 67b
-    fn solve(&self) -> Option<(i32, i32)> {
-        let Pos { x: a1, y: a2 } = self.a;
-        let Pos { x: b1, y: b2 } = self.b;
-        let Pos { x: c1, y: c2 } = self.prize;
-
-        // a1 x + b1 y = c1
-        // a2 x + b2 y = c2
-
-        // (b2 * a1 * x) + (b2 * b1 * y) = (c1 * b2)
-        // (b1 * a2 * x) + (b1 * b2 * y) = (c2 * b1)
-        // (c1 * b2) - (c2 * b1) = (b2 * a1 * x) - (b1 * a2 * x)
-        //                 ...   = ((b2 * a1) - (b1 * a2)) * x
-        // x = (c... ) / (a...)
-
-        let x = ((c1 * b2) - (c2 * b1)) / ((b2 * a1) - (b1 * a2));
-        let y = (c1 - (a1 * x)) / b1;
-
-        if self.a.x * x + self.b.x * y == self.prize.x
-            && self.a.y * x + self.b.y * y == self.prize.y
-        {
-            Some((x, y))
-        } else {
-            None
-        }
-    }
-
-    fn solve_p2(&self) -> Option<(i64, i64)> {
-        let factor: i64 = 10000000000000;
-
-        let Pos { x: a1, y: a2 } = self.a;
-        let Pos { x: b1, y: b2 } = self.b;
-        let Pos { x: c1, y: c2 } = self.prize;
-
-        let a1: i64 = a1 as i64;
-        let a2: i64 = a2 as i64;
-        let b1: i64 = b1 as i64;
-        let b2: i64 = b2 as i64;
-        let c1: i64 = (c1 as i64) + factor;
-        let c2: i64 = (c2 as i64) + factor;
-
-        let x = ((c1 * b2) - (c2 * b1)) / ((b2 * a1) - (b1 * a2));
-        let y = (c1 - (a1 * x)) / b1;
-
-        if (a1 * x + b1 * y == c1) && (a2 * x + b2 * y == c2) {
-            Some((x, y))
-        } else {
-            None
+    fn solve(&self, offset: i64) -> Option<(i64, i64)> {
+        let solution = linear::solve_integer(
+            &[
+                vec![self.a.x.into(), self.b.x.into()],
+                vec![self.a.y.into(), self.b.y.into()],
+            ],
+            &[
+                (self.prize.x + offset).into(),
+                (self.prize.y + offset).into(),
+            ],
+        )?;
+        match solution.integers()?.as_slice() {
+            // buttons can't be pressed a negative number of times.
+            [a, b] if *a >= 0 && *b >= 0 => Some(((*a).try_into().ok()?, (*b).try_into().ok()?)),
+            _ => None,
         }
     }
 
@@ -122,17 +91,36 @@ pub fn part_one(input: &str) -> Option<u32> {
     parse_input(input)
         .ok()?
         .into_iter()
-        .flat_map(|m| m.solve())
+        .flat_map(|m| m.solve(0))
+        .filter(|&(a, b)| a <= 100 && b <= 100)
         .map(|(a, b)| (a as u32) * 3 + (b as u32))
         .sum::<u32>()
         .into()
 }
 
+/// Tries every number of presses, at most 100 of each button as the puzzle allows.
+pub fn part_one_naive(input: &str) -> Option<u32> {
+    parse_input(input)
+        .ok()?
+        .iter()
+        .filter_map(|m| {
+            (0..=100)
+                .flat_map(|a| (0..=100).map(move |b| (a, b)))
+                .filter(|(a, b)| {
+                    a * m.a.x + b * m.b.x == m.prize.x && a * m.a.y + b * m.b.y == m.prize.y
+                })
+                .map(|(a, b)| (a as u32) * 3 + (b as u32))
+                .min()
+        })
+        .sum::<u32>()
+        .into()
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     parse_input(input)
         .ok()?
         .into_iter()
-        .flat_map(|m| m.solve_p2())
+        .flat_map(|m| m.solve(10000000000000))
         .map(|(a, b)| (a as u64) * 3 + (b as u64))
         .sum::<u64>()
         .into()
@@ -141,6 +129,8 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{self, generate};
+    use advent_of_code::variant;
 
     #[test]
     fn test_part_one() {
@@ -153,4 +143,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(459236326669));
     }
+
+    #[test]
+    fn part_one_agrees_with_brute_force() {
+        property::assert_agree(
+            generate::claw_machines,
+            &variant!(part_one_naive),
+            &variant!(part_one),
+        );
+    }
 }
@@ -1,5 +1,6 @@
 advent_of_code::solution!(14);
 
+use advent_of_code::math::number_theory::crt;
 use std::collections::HashMap;
 use std::iter::successors;
 
@@ -138,6 +139,16 @@ impl State {
         ys[ys.len() - 1] > 30 && xs[xs.len() - 1] > 30
     }
 
+    // proportional to the variance of the given coordinate.
+    fn spread(&self, coord: impl Fn(&Pos) -> u16) -> u64 {
+        let n = self.bots.len() as u64;
+        let (sum, sum_sq) = self.bots.iter().fold((0, 0), |(sum, sum_sq), b| {
+            let v = coord(&b.p) as u64;
+            (sum + v, sum_sq + v * v)
+        });
+        n * sum_sq - sum * sum
+    }
+
     fn quadrants(&self) -> [u32; 4] {
         self.bots
             .iter()
@@ -183,17 +194,20 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    successors(State::try_from(input).ok(), |s| s.next())
-        .enumerate()
-        .find(|(i, st)| st.anomaly() || *i > 10000)
-        .map(|(i, st)| {
-            if st.anomaly() {
-                println!("{:?}", st);
-                Some(i)
-            } else {
-                None
-            }
-        })?
+    // x positions repeat every `size.x` steps and y positions every `size.y` steps:
+    // find the most clustered step for each axis and combine them.
+    let start = State::try_from(input).ok()?;
+    let (wx, wy) = (start.size.x as usize, start.size.y as usize);
+    let states: Vec<State> = successors(Some(start), |s| s.next())
+        .take(wx.max(wy))
+        .collect();
+
+    let tx = (0..wx).min_by_key(|&i| states[i].spread(|p| p.x))?;
+    let ty = (0..wy).min_by_key(|&i| states[i].spread(|p| p.y))?;
+    let (t, _) = crt(&[(tx as i64, wx as i64), (ty as i64, wy as i64)])?;
+
+    let st = successors(states.into_iter().next(), |s| s.next()).nth(t as usize)?;
+    st.anomaly().then_some(t as usize)
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 advent_of_code::solution!(15);
 
+use advent_of_code::collections::FastHashMap;
 use std::collections::vec_deque::VecDeque;
-use std::collections::HashMap;
 
 use std::iter::successors;
 
@@ -115,7 +115,7 @@ impl std::fmt::Debug for Tile {
 
 #[derive(Clone)]
 struct State {
-    map: HashMap<Pos, Tile>,
+    map: FastHashMap<Pos, Tile>,
     bot: Pos,
     moves: VecDeque<Dir>,
     size: Pos,
@@ -125,7 +125,7 @@ struct State {
 impl State {
     fn to_p2(&self) -> Option<Self> {
         let mut bot: Option<Pos> = None;
-        let mut new_map: HashMap<Pos, Tile> = HashMap::new();
+        let mut new_map: FastHashMap<Pos, Tile> = FastHashMap::default();
         for (p, t) in self.map.iter() {
             let (p1, p2) = p.to_p2();
             if *t == Tile::Bot {
@@ -273,9 +273,9 @@ impl std::fmt::Debug for State {
 impl TryFrom<&str> for State {
     type Error = ();
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        let (_, map, moves, size): (bool, HashMap<Pos, Tile>, Vec<Dir>, Pos) =
+        let (_, map, moves, size): (bool, FastHashMap<Pos, Tile>, Vec<Dir>, Pos) =
             input.lines().enumerate().try_fold(
-                (true, HashMap::new(), Vec::new(), Pos::init(0, 0)),
+                (true, FastHashMap::default(), Vec::new(), Pos::init(0, 0)),
                 |acc, (y, l)| {
                     let (parse_map, mut map, mut moves, size) = acc;
                     if l.is_empty() {
@@ -1,7 +1,7 @@
 advent_of_code::solution!(16);
 
+use advent_of_code::collections::FastHashMap;
 use std::collections::vec_deque::VecDeque;
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Pos {
@@ -50,7 +50,7 @@ impl Pos {
 
 #[derive(Debug)]
 struct State {
-    map: HashMap<Pos, char>,
+    map: FastHashMap<Pos, char>,
     start: Pos,
     // end: Pos,
     // size: Pos,
@@ -136,7 +136,7 @@ impl TryFrom<&str> for State {
             .collect();
         let start = input.iter().find(|(_, c)| *c == 'S').ok_or(())?.0.clone();
         // let end = input.iter().find(|(_, c)| *c == 'E').ok_or(())?.0.clone();
-        let mut map = HashMap::new();
+        let mut map = FastHashMap::default();
         for (k, v) in input.into_iter() {
             map.insert(k, v);
         }
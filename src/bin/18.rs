@@ -1,7 +1,6 @@
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, part_two: [part_two_naive]);
 
-use std::collections::HashMap;
-use std::collections::HashSet;
+use advent_of_code::collections::{BitGrid, FastHashMap};
 use std::collections::VecDeque;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -14,6 +13,9 @@ impl Pos {
     fn init(x: u8, y: u8) -> Self {
         Self { x, y }
     }
+    fn cell(&self) -> (usize, usize) {
+        (self.x.into(), self.y.into())
+    }
     fn neighbors(&self, max: &Pos) -> Vec<Pos> {
         let mut res = Vec::new();
         if self.x > 0 {
@@ -67,13 +69,23 @@ fn parse_input(input: &str) -> Vec<Pos> {
         .collect()
 }
 
-fn bfs(walls: &HashSet<Pos>, start: Pos, end: &Pos) -> Option<Vec<Pos>> {
+fn walls_grid(coords: &[Pos], end: &Pos) -> BitGrid {
+    let mut walls = BitGrid::new(end.x as usize + 1, end.y as usize + 1);
+    coords.iter().for_each(|p| {
+        let (x, y) = p.cell();
+        walls.insert(x, y);
+    });
+    walls
+}
+
+fn bfs(walls: &BitGrid, start: Pos, end: &Pos) -> Option<Vec<Pos>> {
     let mut queue: VecDeque<Pos> = VecDeque::new();
-    let mut visited: HashSet<Pos> = HashSet::new();
-    let mut parent_map: HashMap<Pos, Pos> = HashMap::new();
+    let mut visited = BitGrid::new(walls.width(), walls.height());
+    let mut parent_map: FastHashMap<Pos, Pos> = FastHashMap::default();
 
     queue.push_back(start.clone());
-    visited.insert(start);
+    let (x, y) = start.cell();
+    visited.insert(x, y);
     while let Some(current) = queue.pop_front() {
         if current == *end {
             let mut path = vec![];
@@ -86,14 +98,11 @@ fn bfs(walls: &HashSet<Pos>, start: Pos, end: &Pos) -> Option<Vec<Pos>> {
             return Some(path);
         }
         for n in current.neighbors(end) {
-            if visited.contains(&n) {
-                continue;
-            }
-            if walls.contains(&n) {
+            let (x, y) = n.cell();
+            if walls.contains(x, y) || !visited.insert(x, y) {
                 continue;
             }
             queue.push_back(n.clone());
-            visited.insert(n.clone());
             parent_map.insert(n, current.clone());
         }
     }
@@ -102,12 +111,9 @@ fn bfs(walls: &HashSet<Pos>, start: Pos, end: &Pos) -> Option<Vec<Pos>> {
 
 pub fn part_one(input: &str) -> Option<usize> {
     let coords = parse_input(input);
-    let (limit, end) = if coords.len() < 100 {
-        (12, Pos::init(6, 6))
-    } else {
-        (1024, Pos::init(70, 70))
-    };
-    let walls: HashSet<Pos> = coords.into_iter().take(limit).collect();
+    let end = exit(&coords);
+    let limit = if coords.len() < 100 { 12 } else { 1024 };
+    let walls = walls_grid(&coords[..limit.min(coords.len())], &end);
     bfs(&walls, Pos::init(0, 0), &end).map(|r| r.len())
 }
 
@@ -118,7 +124,7 @@ fn get_first_failure(coords: &[Pos], end: &Pos) -> usize {
     while last_success < first_failure - 1 {
         let limit = last_success + (first_failure - last_success) / 2;
         // println!(" {} <~~ {}? ~~> {}", last_success, limit, first_failure);
-        let walls: HashSet<Pos> = coords.iter().take(limit + 1).cloned().collect();
+        let walls = walls_grid(&coords[..limit + 1], end);
 
         if bfs(&walls, Pos::init(0, 0), end).is_some() {
             // println!("        -> Success");
@@ -131,20 +137,35 @@ fn get_first_failure(coords: &[Pos], end: &Pos) -> usize {
     first_failure
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let coords = parse_input(input);
-    let end = if coords.len() < 100 {
+fn exit(coords: &[Pos]) -> Pos {
+    if coords.len() < 100 {
         Pos::init(6, 6)
     } else {
         Pos::init(70, 70)
-    };
+    }
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    let coords = parse_input(input);
+    let end = exit(&coords);
     let first_failure = get_first_failure(&coords, &end);
     coords.get(first_failure).unwrap().to_string().into()
 }
 
+/// Drops the bytes one at a time until the exit can't be reached.
+pub fn part_two_naive(input: &str) -> Option<String> {
+    let coords = parse_input(input);
+    let end = exit(&coords);
+    (1..=coords.len())
+        .find(|&fallen| bfs(&walls_grid(&coords[..fallen], &end), Pos::init(0, 0), &end).is_none())
+        .map(|fallen| coords[fallen - 1].to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{self, generate};
+    use advent_of_code::variant;
 
     #[test]
     fn test_part_one() {
@@ -157,4 +178,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some("6,1".into()));
     }
+
+    #[test]
+    fn part_two_agrees_with_byte_by_byte() {
+        property::assert_agree(
+            |rng, _| generate::falling_bytes(rng, 7),
+            &variant!(part_two_naive),
+            &variant!(part_two),
+        );
+    }
 }
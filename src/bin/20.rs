@@ -1,6 +1,6 @@
 advent_of_code::solution!(20);
 
-use std::collections::HashMap;
+use advent_of_code::collections::FastHashMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Pos {
@@ -44,7 +44,7 @@ impl From<char> for PosState {
 
 #[derive(Debug)]
 struct State {
-    m: HashMap<Pos, PosState>,
+    m: FastHashMap<Pos, PosState>,
     end: Pos,
     start: Pos,
 }
@@ -52,7 +52,7 @@ struct State {
 impl State {
     fn new() -> State {
         State {
-            m: HashMap::new(),
+            m: FastHashMap::default(),
             end: Pos::init(0, 0),
             start: Pos::init(0, 0),
         }
@@ -133,8 +133,8 @@ impl State {
     //     }
     // }
 
-    fn get_time_saving_cheats(&self) -> HashMap<usize, usize> {
-        let mut cheats: HashMap<usize, usize> = HashMap::new();
+    fn get_time_saving_cheats(&self) -> FastHashMap<usize, usize> {
+        let mut cheats: FastHashMap<usize, usize> = FastHashMap::default();
 
         for (p, ps) in self.m.iter() {
             if ps.c == '#' {
@@ -142,9 +142,11 @@ impl State {
             }
             for n in p.neighbors() {
                 if let Some(ns) = self.m.get(&n) {
-                    if ns.c == '#' && ns.dist.is_some() && ns.dist < ps.dist {
-                        let cheat_save =
-                            (ps.dist.unwrap() as isize) - (ns.dist.unwrap() as isize) - 1isize;
+                    if ns.c != '#' {
+                        continue;
+                    }
+                    if let (Some(n_dist), Some(p_dist)) = (ns.dist, ps.dist) {
+                        let cheat_save = (p_dist as isize) - (n_dist as isize) - 1isize;
                         if cheat_save > 0 {
                             *cheats.entry(cheat_save as usize).or_default() += 1;
                         }
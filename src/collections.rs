@@ -0,0 +1,344 @@
+/// Collections tuned for grid puzzles.
+///
+/// The standard `HashMap` uses SipHash, which is DoS-resistant but slow for small keys like grid
+/// positions. The aliases below swap in the Fx hash, and the bit grids avoid hashing altogether
+/// when the grid dimensions are known upfront.
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rustc_hash::FxHasher;
+
+pub type FastHashMap<K, V> = HashMap<K, V, FastBuildHasher>;
+pub type FastHashSet<T> = HashSet<T, FastBuildHasher>;
+
+/// Seed of the hashers created from now on, see [`set_hash_seed`].
+static HASH_SEED: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the seed of the Fx hashers of maps and sets created from now on, which changes their
+/// iteration order. Used by `--check-determinism` to catch solutions that depend on it.
+pub fn set_hash_seed(seed: usize) {
+    HASH_SEED.store(seed, Ordering::Relaxed);
+}
+
+/// Creates Fx hashers with the seed set by [`set_hash_seed`]. With the default seed of `0`,
+/// it hashes exactly like `rustc_hash::FxBuildHasher`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastBuildHasher {
+    seed: usize,
+}
+
+impl FastBuildHasher {
+    pub const fn with_seed(seed: usize) -> Self {
+        Self { seed }
+    }
+}
+
+impl Default for FastBuildHasher {
+    fn default() -> Self {
+        Self::with_seed(HASH_SEED.load(Ordering::Relaxed))
+    }
+}
+
+impl BuildHasher for FastBuildHasher {
+    type Hasher = FxHasher;
+
+    fn build_hasher(&self) -> FxHasher {
+        FxHasher::with_seed(self.seed)
+    }
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A dense set of cells on a `width` × `height` grid, one bit per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates an empty grid.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits: vec![0; (width * height).div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is inside the grid.
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Whether `(x, y)` is set. Cells outside the grid are never set.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        let (word, bit) = self.locate(x, y);
+        self.bits[word] & bit != 0
+    }
+
+    /// Sets `(x, y)`, returning whether it was previously unset.
+    ///
+    /// # Panics
+    /// Panics if `(x, y)` is outside the grid.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        assert!(self.in_bounds(x, y), "({x}, {y}) is outside the grid");
+        let (word, bit) = self.locate(x, y);
+        let was_unset = self.bits[word] & bit == 0;
+        self.bits[word] |= bit;
+        was_unset
+    }
+
+    /// Unsets `(x, y)`, returning whether it was previously set.
+    pub fn remove(&mut self, x: usize, y: usize) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        let (word, bit) = self.locate(x, y);
+        let was_set = self.bits[word] & bit != 0;
+        self.bits[word] &= !bit;
+        was_set
+    }
+
+    /// Number of set cells.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|w| *w == 0)
+    }
+
+    /// Unsets every cell.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// Iterates over the set cells as `(x, y)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits.iter().enumerate().flat_map(move |(i, &word)| {
+            (0..WORD_BITS)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| {
+                    let index = i * WORD_BITS + bit;
+                    (index % self.width, index / self.width)
+                })
+        })
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        let index = y * self.width + x;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A dense set of `(cell, direction)` pairs, four bits per cell.
+///
+/// Useful to remember "visited this cell while facing X". Directions are indices in `0..4`,
+/// the mapping to actual directions is up to the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionalBitGrid {
+    width: usize,
+    height: usize,
+    nibbles: Vec<u64>,
+}
+
+const CELLS_PER_WORD: usize = WORD_BITS / 4;
+
+impl DirectionalBitGrid {
+    /// Creates an empty grid.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            nibbles: vec![0; (width * height).div_ceil(CELLS_PER_WORD)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is inside the grid.
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Bit mask of the directions set on `(x, y)`, e.g. `0b0101` for directions 0 and 2.
+    pub fn directions(&self, x: usize, y: usize) -> u8 {
+        if !self.in_bounds(x, y) {
+            return 0;
+        }
+        let (word, shift) = self.locate(x, y);
+        ((self.nibbles[word] >> shift) & 0b1111) as u8
+    }
+
+    /// Whether `(x, y)` is set for `direction`.
+    ///
+    /// # Panics
+    /// Panics if `direction` is not in `0..4`.
+    pub fn contains(&self, x: usize, y: usize, direction: usize) -> bool {
+        assert!(direction < 4, "direction {direction} is not in 0..4");
+        self.directions(x, y) & (1 << direction) != 0
+    }
+
+    /// Whether `(x, y)` is set for any direction.
+    pub fn contains_any(&self, x: usize, y: usize) -> bool {
+        self.directions(x, y) != 0
+    }
+
+    /// Sets `(x, y)` for `direction`, returning whether it was previously unset.
+    ///
+    /// # Panics
+    /// Panics if `(x, y)` is outside the grid or `direction` is not in `0..4`.
+    pub fn insert(&mut self, x: usize, y: usize, direction: usize) -> bool {
+        assert!(self.in_bounds(x, y), "({x}, {y}) is outside the grid");
+        assert!(direction < 4, "direction {direction} is not in 0..4");
+        let (word, shift) = self.locate(x, y);
+        let bit = 1 << (shift + direction);
+        let was_unset = self.nibbles[word] & bit == 0;
+        self.nibbles[word] |= bit;
+        was_unset
+    }
+
+    /// Number of cells set for at least one direction.
+    pub fn cell_count(&self) -> usize {
+        // fold every nibble onto its lowest bit, then count those bits.
+        const LOW_BITS: u64 = 0x1111_1111_1111_1111;
+        self.nibbles
+            .iter()
+            .map(|&w| {
+                let folded = w | (w >> 1) | (w >> 2) | (w >> 3);
+                (folded & LOW_BITS).count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// Collapses the directions, keeping every cell set for at least one of them.
+    pub fn cells(&self) -> BitGrid {
+        let mut grid = BitGrid::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.contains_any(x, y) {
+                    grid.insert(x, y);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Unsets every cell.
+    pub fn clear(&mut self) {
+        self.nibbles.fill(0);
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, usize) {
+        let index = y * self.width + x;
+        (index / CELLS_PER_WORD, (index % CELLS_PER_WORD) * 4)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hash::BuildHasher;
+
+    use rustc_hash::FxBuildHasher;
+
+    use super::{BitGrid, DirectionalBitGrid, FastBuildHasher};
+
+    #[test]
+    fn seeds_hashers() {
+        let unseeded = FastBuildHasher::with_seed(0);
+        assert_eq!(unseeded.hash_one((3, 4)), FxBuildHasher.hash_one((3, 4)));
+        assert_ne!(
+            FastBuildHasher::with_seed(42).hash_one((3, 4)),
+            unseeded.hash_one((3, 4))
+        );
+    }
+
+    #[test]
+    fn sets_and_unsets_cells() {
+        let mut grid = BitGrid::new(71, 71);
+        assert!(grid.is_empty());
+        assert!(grid.insert(70, 70));
+        assert!(!grid.insert(70, 70));
+        assert!(grid.insert(0, 1));
+        assert!(grid.contains(70, 70));
+        assert!(!grid.contains(1, 0));
+        assert!(!grid.contains(71, 0));
+        assert_eq!(grid.len(), 2);
+        assert!(grid.remove(70, 70));
+        assert!(!grid.remove(70, 70));
+        assert_eq!(grid.len(), 1);
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn handles_non_square_grids() {
+        let mut walls = BitGrid::new(10, 5);
+        assert!(walls.insert(3, 4));
+        assert!(walls.insert(9, 0));
+        assert!(walls.contains(3, 4));
+        assert!(!walls.contains(4, 3));
+        assert!(!walls.contains(10, 0));
+        assert!(!walls.contains(0, 5));
+        assert_eq!(walls.iter().collect::<Vec<_>>(), [(9, 0), (3, 4)]);
+    }
+
+    #[test]
+    fn iterates_cells_in_order() {
+        let mut grid = BitGrid::new(10, 10);
+        grid.insert(9, 9);
+        grid.insert(3, 0);
+        grid.insert(0, 7);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [(3, 0), (0, 7), (9, 9)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_inserting_out_of_bounds() {
+        BitGrid::new(3, 3).insert(3, 0);
+    }
+
+    #[test]
+    fn tracks_directions_per_cell() {
+        let mut grid = DirectionalBitGrid::new(130, 130);
+        assert!(grid.insert(5, 7, 0));
+        assert!(grid.insert(5, 7, 3));
+        assert!(!grid.insert(5, 7, 3));
+        assert!(grid.insert(129, 129, 2));
+        assert!(grid.contains(5, 7, 3));
+        assert!(!grid.contains(5, 7, 1));
+        assert!(!grid.contains_any(6, 7));
+        assert_eq!(grid.directions(5, 7), 0b1001);
+        assert_eq!(grid.cell_count(), 2);
+        assert_eq!(
+            grid.cells().iter().collect::<Vec<_>>(),
+            [(5, 7), (129, 129)]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+/// Cycle detection for deterministic simulations.
+///
+/// A simulation is described by an initial state and a `step` function returning the next state,
+/// or `None` once the simulation stops (e.g. a guard leaving the map).
+use crate::collections::FastHashMap;
+use std::hash::Hash;
+
+/// Position of a cycle in a sequence of states: state `start + length` equals state `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` to the step before `start + length` that holds the same state.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Detects a cycle with Brent's algorithm, in constant memory.
+///
+/// `step` is called again on already visited states, so it must be pure.
+/// Returns `None` if the simulation stops before repeating itself.
+pub fn brent<T: Clone + Eq>(initial: T, step: impl Fn(&T) -> Option<T>) -> Option<Cycle> {
+    // find the cycle length by teleporting the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // find the cycle start by walking two states `length` apart until they meet.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Detects a cycle by remembering every visited state.
+///
+/// Calls `step` once per distinct state. Returns `None` if the simulation stops before repeating
+/// itself.
+pub fn find<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<Cycle> {
+    let mut seen: FastHashMap<T, usize> = FastHashMap::default();
+    let mut state = initial;
+    let mut i = 0;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, i);
+        state = next;
+        i += 1;
+    }
+}
+
+/// Returns the state after `n` steps, skipping ahead as soon as a cycle is detected.
+///
+/// Returns `None` if the simulation stops before `n` steps.
+pub fn nth<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+    n: usize,
+) -> Option<T> {
+    let mut seen: FastHashMap<T, usize> = FastHashMap::default();
+    let mut history: Vec<T> = Vec::new();
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return Some(history.swap_remove(cycle.equivalent_step(n)));
+        }
+        let next = step(&state)?;
+        seen.insert(state.clone(), i);
+        history.push(state);
+        state = next;
+    }
+
+    Some(state)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, find, nth, Cycle};
+
+    // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn rho(x: &u32) -> Option<u32> {
+        Some(if *x == 5 { 3 } else { x + 1 })
+    }
+
+    fn stops(x: &u32) -> Option<u32> {
+        (*x < 10).then_some(x + 1)
+    }
+
+    #[test]
+    fn detects_cycles_with_brent() {
+        assert_eq!(
+            brent(0, rho),
+            Some(Cycle {
+                start: 3,
+                length: 3
+            })
+        );
+        assert_eq!(
+            brent(4, rho),
+            Some(Cycle {
+                start: 0,
+                length: 3
+            })
+        );
+        assert_eq!(brent(0, stops), None);
+    }
+
+    #[test]
+    fn detects_cycles_with_hashing() {
+        assert_eq!(
+            find(0, rho),
+            Some(Cycle {
+                start: 3,
+                length: 3
+            })
+        );
+        assert_eq!(find(0, stops), None);
+    }
+
+    #[test]
+    fn fast_forwards_to_step() {
+        assert_eq!(nth(0, rho, 2), Some(2));
+        assert_eq!(nth(0, rho, 6), Some(3));
+        assert_eq!(nth(0, rho, 1_000_000_001), Some(5));
+        assert_eq!(nth(0, stops, 10), Some(10));
+        assert_eq!(nth(0, stops, 11), None);
+        // powers of 3 modulo 7 cycle from the start.
+        assert_eq!(nth(1_u64, |x| Some(x * 3 % 7), 1_000_000_000), Some(4));
+    }
+
+    #[test]
+    fn maps_equivalent_steps() {
+        let cycle = Cycle {
+            start: 3,
+            length: 3,
+        };
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(6), 3);
+        assert_eq!(cycle.equivalent_step(1_000_000_001), 5);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod collections;
+pub mod cycle;
+pub mod math;
+pub mod memo;
+pub mod property;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
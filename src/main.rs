@@ -1,18 +1,24 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, completions, download, read, scaffold, shrink, solve, status, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::{countdown, today};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::cli;
+    use advent_of_code::template::commands::completions::Shell;
+    use advent_of_code::template::commands::status;
+    use advent_of_code::template::runner::Oracle;
+    use advent_of_code::template::{BuildProfile, Day, DaySet, ExportFormat, MergePolicy};
+    use std::path::PathBuf;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: DaySet,
         },
         Read {
             day: Day,
@@ -27,69 +33,165 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            input: Option<String>,
+            example: bool,
+            flamegraph: bool,
+            check_determinism: Option<u32>,
+            random_seed: bool,
+            variants: bool,
+        },
+        Shrink {
+            day: Day,
+            part: u8,
+            oracle: Oracle,
+            input: Option<String>,
         },
         All {
+            days: DaySet,
             release: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            policy: MergePolicy,
+            pin_cpu: Option<usize>,
+            profiles: Option<Vec<BuildProfile>>,
+            history: Option<Day>,
+            export: Option<ExportFormat>,
+            compare_with: Option<PathBuf>,
+        },
+        Status {
+            days: Option<DaySet>,
+            run: Option<Duration>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        #[cfg(feature = "today")]
+        Countdown,
+        Completions {
+            shell: Shell,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let Some(name) = args.subcommand()? else {
+            if args.contains(["-V", "--version"]) {
+                println!("{}", cli::version());
+                process::exit(0);
+            }
+            if args.contains(["-h", "--help"]) {
+                println!("{}", cli::help());
+                process::exit(0);
+            }
+            eprintln!("No command specified.\n\n{}", cli::help());
+            process::exit(1);
+        };
+
+        let Some(command) = cli::find_command(&name) else {
+            eprintln!("Unknown command: {name}\n\n{}", cli::help());
+            process::exit(1);
+        };
+
+        if args.contains(["-h", "--help"]) {
+            println!("{}", cli::usage(command));
+            process::exit(0);
+        }
+
+        let args: Vec<String> = args
+            .finish()
+            .into_iter()
+            .map(|arg| {
+                arg.into_string()
+                    .map_err(|arg| format!("invalid argument {arg:?}."))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut args =
+            cli::parse(command, &args).map_err(|e| format!("{e}\n\n{}", cli::usage(command)))?;
+
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                days: args.arg("DAYS")?.unwrap_or_else(DaySet::all),
             },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "time" => AppArguments::Time {
+                all: args.contains("--all"),
+                store: args.contains("--store"),
+                policy: args.value("--policy")?.unwrap_or_default(),
+                pin_cpu: args.value("--pin-cpu")?,
+                profiles: args.value_from_fn("--profiles", BuildProfile::parse_list)?,
+                history: args.value("--history")?,
+                export: args.value("--export")?,
+                compare_with: args.value("--compare-with")?,
+                days: args.arg("DAYS")?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                days: args.required_arg("DAYS")?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "read" => AppArguments::Read {
+                day: args.required_arg("DAY")?,
+            },
+            "scaffold" => AppArguments::Scaffold {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                day: args.required_arg("DAY")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            "solve" => AppArguments::Solve {
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
+                submit: args.value_from_fn("--submit", cli::parse_part)?,
                 dhat: args.contains("--dhat"),
+                part: args.value_from_fn("--part", cli::parse_part)?,
+                input: args.value("--input")?,
+                example: args.contains("--example"),
+                flamegraph: args.contains("--flamegraph"),
+                check_determinism: args.value("--check-determinism")?,
+                random_seed: args.contains("--random-seed"),
+                variants: args.contains("--variants"),
+                day: args.required_arg("DAY")?,
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "shrink" => {
+                let part = args.required_value("--part", cli::parse_part)?;
+                // NOTE: `--oracle` and `--expect` are declared exclusive in the `cli` table.
+                let oracle = match (args.value("--oracle")?, args.value("--expect")?) {
+                    (Some(variant), _) => Oracle::Variant(variant),
+                    (None, Some(answer)) => Oracle::Answer(answer),
+                    (None, None) => {
+                        return Err("`shrink` needs either `--oracle` or `--expect`.".into())
+                    }
+                };
+                AppArguments::Shrink {
+                    part,
+                    oracle,
+                    input: args.value("--input")?,
+                    day: args.required_arg("DAY")?,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "status" => {
+                // NOTE: the `cli` table already rejects `--timeout` without `--run`.
+                let run = args.contains("--run");
+                let timeout: Option<u64> = args.value("--timeout")?;
+                AppArguments::Status {
+                    run: run.then(|| timeout.map_or(status::DEFAULT_TIMEOUT, Duration::from_secs)),
+                    days: args.arg("DAYS")?,
+                }
             }
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            #[cfg(feature = "today")]
+            "countdown" => AppArguments::Countdown,
+            "completions" => AppArguments::Completions {
+                shell: args.required_arg("SHELL")?,
+            },
+            _ => unreachable!("command `{name}` is described but not parsed"),
         };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+        args.finish();
 
         Ok(app_args)
     }
@@ -102,9 +204,37 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All { days, release } => all::handle(&days, release),
+            AppArguments::Time {
+                history: Some(day), ..
+            } => time::show_history(day),
+            AppArguments::Time {
+                export: Some(format),
+                ..
+            } => time::export(format),
+            AppArguments::Time {
+                compare_with: Some(path),
+                ..
+            } => time::compare_with(&path),
+            AppArguments::Time {
+                days,
+                pin_cpu,
+                profiles: Some(profiles),
+                ..
+            } => time::compare_profiles(days.as_ref(), &profiles, pin_cpu),
+            AppArguments::Time {
+                days,
+                all,
+                store,
+                policy,
+                pin_cpu,
+                ..
+            } => time::handle(days.as_ref(), all, store, policy, pin_cpu),
+            AppArguments::Download { days } => {
+                let mut days: Vec<_> = days.days().into_iter().collect();
+                days.sort();
+                days.into_iter().for_each(download::handle);
+            }
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
@@ -121,24 +251,40 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                part,
+                input,
+                example,
+                flamegraph,
+                check_determinism,
+                random_seed,
+                variants,
+            } => solve::handle(
+                day,
+                release,
+                dhat,
+                submit,
+                &solve::RunArgs {
+                    part,
+                    input,
+                    example,
+                    flamegraph,
+                    check_determinism,
+                    random_seed,
+                    variants,
+                },
+            ),
+            AppArguments::Shrink {
+                day,
+                part,
+                oracle,
+                input,
+            } => shrink::handle(day, part, &oracle, input.as_deref()),
+            AppArguments::Status { days, run } => status::handle(days.as_ref(), run),
+            AppArguments::Completions { shell } => completions::handle(shell),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
+            #[cfg(feature = "today")]
+            AppArguments::Countdown => countdown::handle(),
         },
     };
 }
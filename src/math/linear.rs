@@ -0,0 +1,205 @@
+/// Determinants and exact solving of linear systems.
+use crate::math::Rational;
+
+/// The outcome of solving a linear system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    /// The equations contradict each other.
+    None,
+    /// Exactly one assignment satisfies every equation.
+    Unique(Vec<Rational>),
+    /// The system is underdetermined.
+    Infinite,
+}
+
+impl Solution {
+    /// Returns the values of a unique solution.
+    pub fn unique(&self) -> Option<&[Rational]> {
+        match self {
+            Solution::Unique(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of a unique solution if all of them are integers.
+    pub fn integers(&self) -> Option<Vec<i128>> {
+        self.unique()?
+            .iter()
+            .map(|value| value.to_integer())
+            .collect()
+    }
+}
+
+/// Computes the determinant of a square integer matrix exactly, using the fraction-free
+/// Bareiss algorithm so intermediate values stay integral. Returns `None` if an intermediate value
+/// overflows.
+pub fn determinant<const N: usize>(matrix: [[i128; N]; N]) -> Option<i128> {
+    let mut m = matrix;
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+
+    for k in 0..N {
+        if m[k][k] == 0 {
+            let Some(swap) = (k + 1..N).find(|&i| m[i][k] != 0) else {
+                return Some(0);
+            };
+            m.swap(k, swap);
+            sign = -sign;
+        }
+        for i in k + 1..N {
+            for j in k + 1..N {
+                // NOTE: the division is exact, only `i128::MIN / -1` could overflow.
+                m[i][j] = m[i][j]
+                    .checked_mul(m[k][k])?
+                    .checked_sub(m[i][k].checked_mul(m[k][j])?)?
+                    .checked_div(previous_pivot)?;
+            }
+        }
+        previous_pivot = m[k][k];
+    }
+
+    previous_pivot.checked_mul(sign)
+}
+
+/// Solves `coefficients · x = constants` with Gauss-Jordan elimination over [`Rational`]s.
+///
+/// Returns `None` if an intermediate value overflows.
+///
+/// # Panics
+/// Panics if `constants` or any row of `coefficients` has a mismatching length.
+pub fn solve(coefficients: &[Vec<Rational>], constants: &[Rational]) -> Option<Solution> {
+    assert_eq!(
+        coefficients.len(),
+        constants.len(),
+        "expecting one constant per equation"
+    );
+    let rows = coefficients.len();
+    let cols = coefficients.first().map_or(0, Vec::len);
+
+    // augmented matrix: the last column holds the constants.
+    let mut m: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, constant)| {
+            assert_eq!(row.len(), cols, "expecting rows of equal length");
+            let mut row = row.clone();
+            row.push(*constant);
+            row
+        })
+        .collect();
+
+    let mut pivot_row = 0;
+    for col in 0..cols {
+        if pivot_row == rows {
+            break;
+        }
+        let Some(found) = (pivot_row..rows).find(|&r| !m[r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(pivot_row, found);
+
+        let pivot = m[pivot_row][col];
+        for v in &mut m[pivot_row] {
+            *v = v.checked_div(pivot)?;
+        }
+        let normalized = m[pivot_row].clone();
+
+        for (r, row) in m.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == pivot_row || factor.is_zero() {
+                continue;
+            }
+            for (v, p) in row.iter_mut().zip(&normalized) {
+                *v = v.checked_sub(p.checked_mul(factor)?)?;
+            }
+        }
+        pivot_row += 1;
+    }
+
+    Some(if m[pivot_row..].iter().any(|row| !row[cols].is_zero()) {
+        Solution::None
+    } else if pivot_row < cols {
+        Solution::Infinite
+    } else {
+        Solution::Unique(m[..cols].iter().map(|row| row[cols]).collect())
+    })
+}
+
+/// Same as [`solve`], for a system with integer coefficients.
+pub fn solve_integer(coefficients: &[Vec<i128>], constants: &[i128]) -> Option<Solution> {
+    let coefficients: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .map(|row| row.iter().map(|&v| Rational::from(v)).collect())
+        .collect();
+    let constants: Vec<Rational> = constants.iter().map(|&v| Rational::from(v)).collect();
+    solve(&coefficients, &constants)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{determinant, solve_integer, Solution};
+    use crate::math::Rational;
+
+    #[test]
+    fn computes_determinants() {
+        assert_eq!(determinant([[94, 22], [34, 67]]), Some(5550));
+        assert_eq!(determinant([[0, 1], [1, 0]]), Some(-1));
+        assert_eq!(determinant([[2, 0, 1], [1, 3, 2], [1, 1, 2]]), Some(6));
+        assert_eq!(determinant([[1, 2], [2, 4]]), Some(0));
+    }
+
+    #[test]
+    fn detects_overflowing_determinants() {
+        let huge = i128::MAX / 2;
+        assert_eq!(determinant([[huge, 1], [1, huge]]), None);
+        assert_eq!(determinant([[0, i128::MIN], [1, 0]]), None);
+        assert_eq!(determinant([[i128::MIN, 0], [0, 1]]), Some(i128::MIN));
+    }
+
+    #[test]
+    fn solves_unique_systems() {
+        let res = solve_integer(&[vec![94, 22], vec![34, 67]], &[8400, 5400]).unwrap();
+        assert_eq!(res.integers(), Some(vec![80, 40]));
+
+        let res = solve_integer(&[vec![2, 0], vec![0, 3]], &[1, 1]).unwrap();
+        assert_eq!(
+            res.unique(),
+            Some([Rational::new(1, 2), Rational::new(1, 3)].as_slice())
+        );
+        assert_eq!(res.integers(), None);
+    }
+
+    #[test]
+    fn detects_inconsistent_systems() {
+        let res = solve_integer(&[vec![1, 2], vec![2, 4]], &[3, 7]).unwrap();
+        assert_eq!(res, Solution::None);
+    }
+
+    #[test]
+    fn detects_underdetermined_systems() {
+        let res = solve_integer(&[vec![1, 2], vec![2, 4]], &[3, 6]).unwrap();
+        assert_eq!(res, Solution::Infinite);
+
+        let res = solve_integer(&[vec![1, 1, 1]], &[3]).unwrap();
+        assert_eq!(res, Solution::Infinite);
+    }
+
+    #[test]
+    fn detects_overflow() {
+        let huge = i128::MAX / 2;
+        let res = solve_integer(&[vec![huge, 1], vec![1, huge]], &[huge, 1]);
+        assert_eq!(res, None);
+
+        // large but cancelling values stay solvable.
+        let res = solve_integer(&[vec![huge, 0], vec![0, huge]], &[huge, 2 * huge]).unwrap();
+        assert_eq!(res.integers(), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn handles_overdetermined_systems() {
+        let res = solve_integer(&[vec![1, 0], vec![0, 1], vec![1, 1]], &[1, 2, 3]).unwrap();
+        assert_eq!(res.integers(), Some(vec![1, 2]));
+    }
+}
@@ -0,0 +1,6 @@
+/// Exact arithmetic helpers for puzzles that boil down to algebra.
+pub mod linear;
+pub mod number_theory;
+pub mod rational;
+
+pub use rational::Rational;
@@ -0,0 +1,162 @@
+//! Integer helpers: divisibility, modular arithmetic and decimal digit manipulation.
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. `lcm(x, 0)` is `0`.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is `gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Computes `base ^ exp mod modulus` by repeated squaring.
+///
+/// # Panics
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must not be zero");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut exp = exp;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    // NOTE: the result is reduced modulo a `u64`, so it always fits.
+    result as u64
+}
+
+/// Returns `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` is invertible.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a, modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem.
+///
+/// Moduli don't need to be pairwise coprime. Returns `(x, lcm of the moduli)` with `x` being the
+/// smallest non-negative solution, or `None` if the congruences are incompatible.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0_i64, 1_i64), |(x, m), &(residue, modulus)| {
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let k = i128::from(diff / g) * i128::from(p) % i128::from(step);
+            let combined = m.checked_mul(step)?;
+            let x = (i128::from(x) + i128::from(m) * k).rem_euclid(i128::from(combined));
+            Some((i64::try_from(x).ok()?, combined))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of decimal digits of `n`. `0` has one digit.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Splits `n` before its last `k` digits, e.g. `split_digits(1234, 1) == (123, 4)`.
+pub fn split_digits(n: u64, k: u32) -> (u64, u64) {
+    let div = 10u64.pow(k);
+    (n / div, n % div)
+}
+
+/// Writes the digits of `b` after the digits of `a`, e.g. `concat_digits(12, 345) == 12345`.
+pub fn concat_digits(a: u64, b: u64) -> u64 {
+    a * 10u64.pow(digit_count(b)) + b
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+        assert_eq!(lcm(0, 3), 0);
+    }
+
+    #[test]
+    fn computes_extended_gcd() {
+        for (a, b) in [(240, 46), (-7, 3), (5, 0), (17, 17)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(101, 103), Some(51));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(7, 101), (54, 103)]), Some((2835, 10403)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(split_digits(253000, 3), (253, 0));
+        assert_eq!(concat_digits(12, 345), 12345);
+        assert_eq!(concat_digits(12, 0), 120);
+    }
+}
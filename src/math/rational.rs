@@ -0,0 +1,363 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact fraction of two [`i128`], always kept in lowest terms with a positive denominator.
+///
+/// Common factors are cancelled before multiplying, so values only overflow if the result itself
+/// doesn't fit. The operators then panic, the `checked_*` methods return `None` instead.
+///
+/// # Display
+/// Integers display without a denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { numer: 0, denom: 1 };
+    pub const ONE: Self = Self { numer: 1, denom: 1 };
+
+    /// Creates the fraction `numer / denom`, reduced to lowest terms.
+    ///
+    /// # Panics
+    /// Panics if `denom` is zero, or if the reduced fraction doesn't fit, see [`Self::checked_new`].
+    #[must_use]
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "rational with a zero denominator");
+        Self::checked_new(numer, denom).expect("rational overflowed")
+    }
+
+    /// Creates the fraction `numer / denom`, reduced to lowest terms. Returns `None` if `denom` is
+    /// zero, or if the denominator would be `2^127` after moving the sign to the numerator,
+    /// e.g. for `1 / i128::MIN`.
+    #[must_use]
+    pub fn checked_new(numer: i128, denom: i128) -> Option<Self> {
+        if denom == 0 {
+            return None;
+        }
+        // in unsigned magnitudes, `i128::MIN` has no positive counterpart.
+        let divisor = gcd(numer.unsigned_abs(), denom.unsigned_abs());
+        let magnitude = numer.unsigned_abs() / divisor;
+        let numer = if (numer < 0) == (denom < 0) {
+            i128::try_from(magnitude).ok()?
+        } else {
+            0_i128.checked_sub_unsigned(magnitude)?
+        };
+        Some(Self {
+            numer,
+            denom: i128::try_from(denom.unsigned_abs() / divisor).ok()?,
+        })
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numer == 0
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denom == 1
+    }
+
+    /// Returns the value as an integer if it has no fractional part, `None` otherwise.
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    /// Returns `1 / self`.
+    ///
+    /// # Panics
+    /// Panics if `self` is zero or its numerator is `i128::MIN`.
+    #[must_use]
+    pub fn recip(self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+
+    /// `1 / self`, or `None` if `self` is zero or its numerator is `i128::MIN`.
+    #[must_use]
+    pub fn checked_recip(self) -> Option<Self> {
+        Self::checked_new(self.denom, self.numer)
+    }
+
+    /// # Panics
+    /// Panics if the numerator is `i128::MIN`.
+    #[must_use]
+    pub fn abs(self) -> Self {
+        self.checked_abs()
+            .expect("rational absolute value overflowed")
+    }
+
+    /// `|self|`, or `None` if the numerator is `i128::MIN`.
+    #[must_use]
+    pub fn checked_abs(self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_abs()?,
+            denom: self.denom,
+        })
+    }
+
+    /// `self + rhs`, or `None` on overflow.
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // over the least common multiple of the denominators rather than their product.
+        let divisor =
+            i128::try_from(gcd(self.denom.unsigned_abs(), rhs.denom.unsigned_abs())).ok()?;
+        let numer = self
+            .numer
+            .checked_mul(rhs.denom / divisor)?
+            .checked_add(rhs.numer.checked_mul(self.denom / divisor)?)?;
+        Self::checked_new(numer, (self.denom / divisor).checked_mul(rhs.denom)?)
+    }
+
+    /// `self - rhs`, or `None` on overflow.
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    /// `self * rhs`, or `None` on overflow.
+    #[must_use]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // both are in lowest terms, so the product is once factors are cancelled crosswise.
+        // NOTE: a divisor of a (positive) denominator always fits.
+        let cancel = |numer: i128, denom: i128| {
+            i128::try_from(gcd(numer.unsigned_abs(), denom.unsigned_abs())).ok()
+        };
+        let (a, b) = (
+            cancel(self.numer, rhs.denom)?,
+            cancel(rhs.numer, self.denom)?,
+        );
+        Some(Self {
+            numer: (self.numer / a).checked_mul(rhs.numer / b)?,
+            denom: (self.denom / b).checked_mul(rhs.denom / a)?,
+        })
+    }
+
+    /// `self / rhs`, or `None` on overflow or if `rhs` is zero.
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.checked_recip()?)
+    }
+
+    /// `-self`, or `None` on overflow.
+    #[must_use]
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom,
+        })
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Rational {
+            fn from(value: $t) -> Self {
+                Self { numer: i128::from(value), denom: 1 }
+            }
+        }
+    )*};
+}
+
+impl_from_int!(i8 i16 i32 i64 i128 u8 u16 u32 u64);
+
+/* -------------------------------------------------------------------------- */
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("rational addition overflowed")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs)
+            .expect("rational subtraction overflowed")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs)
+            .expect("rational multiplication overflowed")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("rational negation overflowed")
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // compares the continued fractions, which cross-multiplying could overflow on.
+        let ((mut a, mut b), (mut c, mut d)) =
+            ((self.numer, self.denom), (other.numer, other.denom));
+        loop {
+            let (q1, q2) = (a.div_euclid(b), c.div_euclid(d));
+            if q1 != q2 {
+                return q1.cmp(&q2);
+            }
+            let (r1, r2) = (a.rem_euclid(b), c.rem_euclid(d));
+            if r1 == 0 || r2 == 0 {
+                // a zero remainder is the smaller fractional part.
+                return (r1 != 0).cmp(&(r2 != 0));
+            }
+            // r1 / b < r2 / d exactly when d / r2 < b / r1.
+            ((a, b), (c, d)) = ((d, r2), (b, r1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rational;
+
+    #[test]
+    fn normalizes_fractions() {
+        let r = Rational::new(10, -4);
+        assert_eq!(r.numer(), -5);
+        assert_eq!(r.denom(), 2);
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+    }
+
+    #[test]
+    fn performs_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(1, 3);
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(-half, Rational::new(-1, 2));
+    }
+
+    #[test]
+    fn displays_integers_without_denominator() {
+        let r = Rational::new(6, -4);
+        assert_eq!(r.to_string(), "-3/2");
+        assert_eq!((r * Rational::from(2)).to_string(), "-3");
+    }
+
+    #[test]
+    fn handles_minimum_values() {
+        let min = i128::MIN;
+        assert_eq!(Rational::new(min, 1).numer(), min);
+        assert_eq!(Rational::new(min, min), Rational::ONE);
+        assert_eq!(Rational::new(min, 2).numer(), min / 2);
+        assert_eq!(Rational::checked_new(1, min), None);
+        assert_eq!(Rational::checked_new(min, -1), None);
+        assert_eq!(
+            Rational::new(min, 4).checked_abs(),
+            Some(Rational::new(-(min / 4), 1))
+        );
+        assert_eq!(Rational::from(min).checked_abs(), None);
+        assert_eq!(Rational::from(min).checked_neg(), None);
+        assert_eq!(Rational::ONE.checked_div(Rational::from(min)), None);
+        assert_eq!(
+            Rational::from(min).checked_mul(Rational::new(1, 2)),
+            Some(Rational::from(min / 2))
+        );
+    }
+
+    #[test]
+    fn checks_integers() {
+        assert_eq!(Rational::new(8, 4).to_integer(), Some(2));
+        assert_eq!(Rational::new(8, 3).to_integer(), None);
+    }
+
+    #[test]
+    fn orders_fractions() {
+        assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+        assert!(Rational::new(2, 3) > Rational::new(3, 5));
+    }
+
+    #[test]
+    fn cancels_before_multiplying() {
+        let big = Rational::new(i128::MAX, 3);
+        assert_eq!(big * Rational::new(3, i128::MAX), Rational::ONE);
+        assert_eq!(
+            Rational::new(1, i128::MAX - 1) + Rational::new(1, i128::MAX - 1),
+            Rational::new(2, i128::MAX - 1)
+        );
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(big.checked_add(big), None);
+        assert_eq!(Rational::ONE.checked_div(Rational::ZERO), None);
+    }
+
+    #[test]
+    fn orders_large_fractions() {
+        let max = i128::MAX;
+        assert!(Rational::new(max - 1, max) > Rational::new(max - 2, max - 1));
+        assert!(Rational::new(3, 7) < Rational::new(4, 9));
+        assert!(Rational::new(-max, 2) < Rational::new(-max + 1, 2));
+        assert!(Rational::new(max, 7) > Rational::new(max - 1, 7));
+        assert_eq!(
+            Rational::new(max, 5).cmp(&Rational::new(max, 5)),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_denominator() {
+        let _ = Rational::new(1, 0);
+    }
+}
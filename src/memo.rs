@@ -0,0 +1,110 @@
+/// Memoization with an explicit scope.
+///
+/// Unlike a global cache (e.g. the `#[memoize]` attribute), a [`Memo`] lives as long as the value
+/// that owns it. Creating it inside a solution part means every run starts from a cold cache,
+/// which keeps `--time` benchmarks honest.
+use crate::collections::FastHashMap;
+use std::hash::Hash;
+
+/// A cache mapping arguments of a (usually recursive) function to its results.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: FastHashMap<K, V>,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: FastHashMap::default(),
+        }
+    }
+
+    /// Returns the cached value for `key`, computing and storing it with `compute` if missing.
+    ///
+    /// `compute` receives the cache itself so it can recurse through it.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Drops every cached entry.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Memo;
+
+    fn count_calls(memo: &mut Memo<u32, u32>, calls: &mut u32, n: u32) -> u32 {
+        *calls += 1;
+        if n == 0 {
+            return 0;
+        }
+        memo.get_or_insert_with(n, |memo| count_calls(memo, calls, n - 1) + 1)
+    }
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn recurses_through_cache() {
+        assert_eq!(fib(&mut Memo::new(), 90), 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn computes_each_key_once() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        assert_eq!(count_calls(&mut memo, &mut calls, 10), 10);
+        assert_eq!(calls, 11);
+        assert_eq!(memo.len(), 10);
+
+        calls = 0;
+        assert_eq!(count_calls(&mut memo, &mut calls, 10), 10);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn clear_resets_cache() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        count_calls(&mut memo, &mut calls, 5);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(&5), None);
+    }
+}
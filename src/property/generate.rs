@@ -0,0 +1,133 @@
+/// Generators of random puzzle inputs, in the format of the days they are named after.
+///
+/// `size` scales an input: the number of lines, machines or files.
+///
+/// NOTE: there is no generator of walled grids yet. None of the grid days (06, 16 and 20) has a
+/// second implementation to compare with, add one together with the first variant of such a day.
+use super::Rng;
+
+/// Bytes falling on a memory space like day 18 of `side` × `side` cells, in random order: every
+/// cell but the top left and bottom right corners, so the path between them ends up cut off.
+pub fn falling_bytes(rng: &mut Rng, side: usize) -> String {
+    let mut cells: Vec<(usize, usize)> = (0..side)
+        .flat_map(|y| (0..side).map(move |x| (x, y)))
+        .filter(|&cell| cell != (0, 0) && cell != (side - 1, side - 1))
+        .collect();
+    // Fisher-Yates shuffle.
+    for i in (1..cells.len()).rev() {
+        #[allow(clippy::cast_possible_truncation)]
+        cells.swap(i, rng.range(0, i as u64) as usize);
+    }
+    cells.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
+
+/// `size` calibration equations like day 07, `result: a b c`. About half of them can be made true
+/// by inserting `+`, `*` and, if `concat` is set, `||` operators.
+pub fn equations(rng: &mut Rng, size: usize, concat: bool) -> String {
+    let operators: &[fn(u64, u64) -> Option<u64>] = if concat {
+        &[u64::checked_add, u64::checked_mul, concat_checked]
+    } else {
+        &[u64::checked_add, u64::checked_mul]
+    };
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let numbers: Vec<u64> = (0..rng.range(2, 6)).map(|_| rng.range(1, 99)).collect();
+        let solvable = numbers[1..]
+            .iter()
+            .try_fold(numbers[0], |acc, &n| rng.choose(operators)(acc, n))
+            .filter(|_| rng.chance(0.5));
+        let result = solvable.unwrap_or_else(|| rng.range(1, 10_000));
+
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        input += &format!("{result}: {}\n", numbers.join(" "));
+    }
+    input
+}
+
+fn concat_checked(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10_u64.checked_pow(b.checked_ilog10().unwrap_or(0) + 1)?)?
+        .checked_add(b)
+}
+
+/// A dense disk map like day 09 with `size` files of 1 to 9 blocks, separated by 0 to 9 free blocks.
+pub fn disk_map(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for file in 0..size.max(1) {
+        if file > 0 {
+            input += &rng.range(0, 9).to_string();
+        }
+        input += &rng.range(1, 9).to_string();
+    }
+    input + "\n"
+}
+
+/// `size` claw machines like day 13, with buttons moving by 10 to 99 on each axis. Most prizes
+/// can be reached with at most 100 presses of each button, the others are random.
+pub fn claw_machines(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let a = (rng.range(10, 99), rng.range(10, 99));
+            let b = (rng.range(10, 99), rng.range(10, 99));
+            let prize = if rng.chance(0.75) {
+                let (presses_a, presses_b) = (rng.range(0, 100), rng.range(0, 100));
+                (
+                    presses_a * a.0 + presses_b * b.0,
+                    presses_a * a.1 + presses_b * b.1,
+                )
+            } else {
+                (rng.range(0, 20_000), rng.range(0, 20_000))
+            };
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a.0, a.1, b.0, b.1, prize.0, prize.1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{claw_machines, disk_map, equations, falling_bytes};
+    use crate::property::Rng;
+
+    #[test]
+    fn generates_falling_bytes() {
+        let input = falling_bytes(&mut Rng::new(1), 4);
+        let mut cells: Vec<&str> = input.lines().collect();
+        assert_eq!(cells.len(), 14);
+        cells.sort_unstable();
+        cells.dedup();
+        assert_eq!(cells.len(), 14);
+        assert!(!cells.contains(&"0,0") && !cells.contains(&"3,3"));
+    }
+
+    #[test]
+    fn generates_equations() {
+        let input = equations(&mut Rng::new(2), 10, true);
+        assert_eq!(input.lines().count(), 10);
+        for line in input.lines() {
+            let (result, numbers) = line.split_once(": ").unwrap();
+            assert!(result.parse::<u64>().is_ok());
+            assert!(numbers.split(' ').all(|n| n.parse::<u64>().is_ok()));
+        }
+    }
+
+    #[test]
+    fn generates_disk_maps() {
+        let input = disk_map(&mut Rng::new(3), 4);
+        assert_eq!(input.trim_end().len(), 7);
+        assert!(input.trim_end().bytes().step_by(2).all(|b| b != b'0'));
+    }
+
+    #[test]
+    fn generates_claw_machines() {
+        let input = claw_machines(&mut Rng::new(4), 3);
+        assert_eq!(input.split("\n\n").count(), 3);
+        assert!(input.starts_with("Button A: X+"));
+        assert!(input.lines().nth(2).unwrap().starts_with("Prize: X="));
+    }
+}
@@ -0,0 +1,275 @@
+/// Property-based tests: random inputs fed to a reference implementation and the real solution.
+///
+/// Example files cover a single case. A property test generates many inputs of growing size with
+/// a day's generator (see [`generate`]) and checks that a slow but obviously correct variant of a
+/// part agrees with the optimized one. Failures are shrunk to the smallest generated input that
+/// still fails, so they are easy to debug or to add as an example.
+///
+/// ```ignore
+/// #[test]
+/// fn part_one_agrees_with_brute_force() {
+///     property::assert_agree(
+///         generate::claw_machines,
+///         &variant!(part_one_naive),
+///         &variant!(part_one),
+///     );
+/// }
+/// ```
+use std::fmt::{self, Display};
+use std::{env, panic};
+
+use crate::template::runner::Variant;
+
+pub mod generate;
+pub mod shrink;
+
+/// Environment variable to change the seed of the generated inputs, e.g. to reproduce a failure.
+pub const SEED_VAR: &str = "AOC_PROPERTY_SEED";
+
+/// Attempts per size when looking for a smaller failing input.
+const SHRINK_ATTEMPTS: u64 = 20;
+
+/// A small, seedable pseudo-random number generator (SplitMix64), good enough to generate inputs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `min..=max`.
+    ///
+    /// # Panics
+    /// Panics if `min > max`.
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "empty range {min}..={max}");
+        match (max - min).checked_add(1) {
+            Some(len) => min + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let unit = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        unit < p
+    }
+
+    /// A random element of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        #[allow(clippy::cast_possible_truncation)]
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+}
+
+/// How many inputs a property test generates, and how large they get.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cases: usize,
+    /// Sizes grow linearly from 1 to this, what a size means is up to the generator.
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    /// 100 cases up to size 20, seeded from [`SEED_VAR`] if set.
+    fn default() -> Self {
+        Self {
+            cases: 100,
+            max_size: 20,
+            seed: env::var(SEED_VAR)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// A generated input the solution and the reference disagree on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub reference: &'static str,
+    pub expected: String,
+    pub solution: &'static str,
+    pub actual: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "`{}` disagrees with `{}` on this input (seed {}, size {}):",
+            self.solution, self.reference, self.seed, self.size
+        )?;
+        writeln!(f, "{}", self.input.trim_end())?;
+        write!(
+            f,
+            "`{}` answered {}, `{}` answered {}.",
+            self.reference, self.expected, self.solution, self.actual
+        )
+    }
+}
+
+/// Feeds generated inputs to `reference` and `solution` and returns the smallest input found
+/// they disagree on. A panic counts as an answer, so it is reported like a wrong one.
+pub fn check<T: Display>(
+    config: &Config,
+    generate: impl Fn(&mut Rng, usize) -> String,
+    reference: &Variant<T>,
+    solution: &Variant<T>,
+) -> Result<(), Failure> {
+    let try_case = |seed: u64, size: usize| -> Result<(), Failure> {
+        let input = generate(&mut Rng::new(seed), size);
+        let expected = outcome(reference, &input);
+        let actual = outcome(solution, &input);
+        if expected == actual {
+            return Ok(());
+        }
+        Err(Failure {
+            seed,
+            size,
+            input,
+            reference: reference.name,
+            expected,
+            solution: solution.name,
+            actual,
+        })
+    };
+
+    for case in 0..config.cases {
+        let size =
+            1 + case * config.max_size.saturating_sub(1) / config.cases.max(2).saturating_sub(1);
+        let seed = config.seed.wrapping_add(case as u64);
+        let Err(failure) = try_case(seed, size) else {
+            continue;
+        };
+
+        // look for a smaller failing input, trying the smallest sizes first.
+        for smaller in 1..size {
+            for attempt in 0..SHRINK_ATTEMPTS {
+                let seed = seed.wrapping_mul(SHRINK_ATTEMPTS).wrapping_add(attempt);
+                try_case(seed, smaller)?;
+            }
+        }
+        return Err(failure);
+    }
+    Ok(())
+}
+
+/// Runs [`check`] with the default [`Config`], panicking with the smallest failing input.
+pub fn assert_agree<T: Display>(
+    generate: impl Fn(&mut Rng, usize) -> String,
+    reference: &Variant<T>,
+    solution: &Variant<T>,
+) {
+    if let Err(failure) = check(&Config::default(), generate, reference, solution) {
+        panic!("{failure}");
+    }
+}
+
+/// The answer of `variant`, described so that it can be compared and printed.
+pub(crate) fn outcome<T: Display>(variant: &Variant<T>, input: &str) -> String {
+    match panic::catch_unwind(|| (variant.func)(input)) {
+        Ok(Some(answer)) => format!("`{answer}`"),
+        Ok(None) => "nothing".into(),
+        Err(_) => "with a panic".into(),
+    }
+}
+
+/// The answer of `variant`, or `None` if it has none or panics, e.g. on malformed input.
+pub(crate) fn answer<T: Display>(variant: &Variant<T>, input: &str) -> Option<String> {
+    panic::catch_unwind(|| (variant.func)(input))
+        .ok()
+        .flatten()
+        .map(|answer| answer.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Config, Rng};
+    use crate::template::runner::Variant;
+
+    fn sum(input: &str) -> Option<u64> {
+        input
+            .split_whitespace()
+            .map(|n| n.parse::<u64>().ok())
+            .sum()
+    }
+
+    /// Forgets numbers above 90.
+    fn buggy_sum(input: &str) -> Option<u64> {
+        input
+            .split_whitespace()
+            .map(|n| n.parse::<u64>().ok().filter(|n| *n <= 90).or(Some(0)))
+            .sum()
+    }
+
+    fn numbers(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| rng.range(0, 99).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    const SUM: Variant<u64> = crate::variant!(sum);
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            let n = a.range(3, 5);
+            assert!((3..=5).contains(&n));
+            assert_eq!(n, b.range(3, 5));
+        }
+        assert_eq!(a.range(2, 2), 2);
+        assert_eq!(Rng::new(1).range(0, u64::MAX), Rng::new(1).next_u64());
+        assert!(!a.chance(0.0) && a.chance(1.0));
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let same = Variant {
+            name: "same",
+            func: sum,
+        };
+        assert_eq!(check(&Config::default(), numbers, &SUM, &same), Ok(()));
+    }
+
+    #[test]
+    fn reports_small_failing_inputs() {
+        let failure = check(
+            &Config::default(),
+            numbers,
+            &SUM,
+            &crate::variant!(buggy_sum),
+        )
+        .unwrap_err();
+        assert_eq!(failure.size, 1);
+        assert!(failure.input.parse::<u64>().unwrap() > 90);
+        assert_eq!(failure.expected, format!("`{}`", failure.input));
+        assert_eq!(failure.actual, "`0`");
+        assert!(failure
+            .to_string()
+            .starts_with("`buggy_sum` disagrees with `sum` on this input"));
+    }
+}
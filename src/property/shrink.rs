@@ -0,0 +1,129 @@
+//! Delta debugging of puzzle inputs: removes parts of an input as long as it keeps failing.
+//!
+//! Inputs are reduced at the granularity puzzles are written in: blocks separated by blank lines,
+//! the columns of grids, then lines. Each level removes halves first, then quarters, down to
+//! single units, and the levels repeat until nothing more can be removed.
+
+/// The smallest input found that `is_failing` still holds for, given that it holds for `input`.
+pub fn minimize(input: &str, mut is_failing: impl FnMut(&str) -> bool) -> String {
+    let trailing_newline = input.ends_with('\n');
+    let finish = |lines: &[String]| {
+        let mut text = lines.join("\n");
+        if trailing_newline {
+            text.push('\n');
+        }
+        text
+    };
+
+    let mut lines: Vec<String> = input.lines().map(String::from).collect();
+    loop {
+        let before = finish(&lines).len();
+
+        let blocks: Vec<Vec<String>> = lines
+            .split(String::is_empty)
+            .map(<[String]>::to_vec)
+            .collect();
+        if blocks.len() > 1 {
+            let join = |blocks: &[Vec<String>]| blocks.join(&String::new());
+            let blocks = reduce(blocks, |b| finish(&join(b)), &mut is_failing);
+            lines = join(&blocks);
+        }
+
+        if is_grid(&lines) {
+            let grid: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+            let select = |columns: &[usize]| -> Vec<String> {
+                grid.iter()
+                    .map(|row| columns.iter().map(|&x| row[x]).collect())
+                    .collect()
+            };
+            let columns = reduce(
+                (0..grid[0].len()).collect(),
+                |c| finish(&select(c)),
+                &mut is_failing,
+            );
+            lines = select(&columns);
+        }
+
+        lines = reduce(lines, |l| finish(l), &mut is_failing);
+
+        if finish(&lines).len() == before {
+            return finish(&lines);
+        }
+    }
+}
+
+/// Whether `lines` are the rows of a grid, i.e. several lines of the same length.
+fn is_grid(lines: &[String]) -> bool {
+    lines.len() > 1
+        && lines[0].chars().count() > 1
+        && lines
+            .iter()
+            .all(|l| l.chars().count() == lines[0].chars().count())
+}
+
+/// Removes chunks of `units` while `is_failing` holds for the rendered rest. Never removes all of them.
+fn reduce<T: Clone>(
+    mut units: Vec<T>,
+    render: impl Fn(&[T]) -> String,
+    is_failing: &mut impl FnMut(&str) -> bool,
+) -> Vec<T> {
+    let mut chunk = (units.len() / 2).max(1);
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+        while start < units.len() && units.len() > 1 {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<T> = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .cloned()
+                .collect();
+            if !candidate.is_empty() && is_failing(&render(&candidate)) {
+                units = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if chunk == 1 && !removed {
+            return units;
+        }
+        chunk = (chunk / 2).max(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::minimize;
+
+    #[test]
+    fn removes_lines() {
+        let input: String = (1..=20).map(|n| format!("{n}\n")).collect();
+        let failing = |s: &str| s.lines().any(|l| l == "7") && s.lines().any(|l| l == "13");
+        assert_eq!(minimize(&input, failing), "7\n13\n");
+    }
+
+    #[test]
+    fn removes_blocks() {
+        let input = "a\nb\n\nc\nX\n\nd\ne";
+        assert_eq!(minimize(input, |s| s.contains('X')), "X");
+
+        // lines of a block that only fail together are kept.
+        let input = "a\nb\n\nc\nbad\n\nd\ne\n";
+        let failing = |s: &str| {
+            s.split("\n\n")
+                .any(|b| b.contains("bad") && b.trim_end().lines().count() == 2)
+        };
+        assert_eq!(minimize(input, failing), "c\nbad\n");
+    }
+
+    #[test]
+    fn removes_grid_columns() {
+        let input = "#####\n#..X#\n#.#.#\n#####\n";
+        let failing = |s: &str| s.lines().any(|l| l.contains(".X"));
+        assert_eq!(minimize(input, failing), ".X\n");
+    }
+}
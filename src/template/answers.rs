@@ -0,0 +1,182 @@
+/// Answers accepted by Advent of Code, recorded when a submission succeeds.
+/// They are the source of the progress table in the README, see `readme_progress`.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+pub static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// An accepted answer to one part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    /// `None` if the star was imported from the README and the answer itself is unknown.
+    pub answer: Option<String>,
+}
+
+/// Represents the accepted answers of a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Whether an answers file was recorded yet.
+    pub fn file_exists() -> bool {
+        Path::new(ANSWERS_FILE_PATH).exists()
+    }
+
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns no answers.
+    /// Any other error is returned, so that callers don't overwrite a file they couldn't read.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            s => s
+                .map_err(|x| x.to_string())
+                .and_then(Answers::try_from)
+                .map_err(|e| format!("{ANSWERS_FILE_PATH}: {e}")),
+        }
+    }
+
+    /// Records an accepted answer, replacing a previous one for the same part.
+    pub fn record(&mut self, day: Day, part: u8, answer: Option<String>) {
+        self.data.retain(|a| (a.day, a.part) != (day, part));
+        self.data.push(Answer { day, part, answer });
+        self.data.sort_by_key(|a| (a.day, a.part));
+    }
+
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.data.iter().any(|a| a.day == day && a.part == part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|answer| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("day".into(), JsonValue::String(answer.day.to_string()));
+                map.insert("part".into(), JsonValue::Number(f64::from(answer.part)));
+                map.insert(
+                    "answer".into(),
+                    answer
+                        .answer
+                        .clone()
+                        .map_or(JsonValue::Null, JsonValue::String),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = json_data
+            .iter()
+            .map(|value| {
+                let json = value
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected answer to be a JSON object.")?;
+
+                let day = json
+                    .get("day")
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|day| Day::from_str(day).ok())
+                    .ok_or("Expected answer.day to be a Day struct.")?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let part = json
+                    .get("part")
+                    .and_then(|v| v.get::<f64>())
+                    .map(|p| *p as u8)
+                    .filter(|p| matches!(p, 1 | 2))
+                    .ok_or("Expected answer.part to be 1 or 2.")?;
+
+                Ok(Answer {
+                    day,
+                    part,
+                    answer: json.get("answer").and_then(|v| v.get::<String>()).cloned(),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Answers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, Some("12".into()));
+        answers.record(day!(1), 2, None);
+        answers.record(day!(2), 1, Some("13".into()));
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.data[1].answer.as_deref(), Some("13"));
+        assert!(answers.is_solved(day!(1), 2));
+        assert!(!answers.is_solved(day!(1), 1));
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, Some("4242".into()));
+        answers.record(day!(1), 2, None);
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        let json = r#"{ "data": [{ "day": "01", "part": 3, "answer": "1" }] }"#;
+        assert!(Answers::try_from(json.to_string()).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+/// Conditions a benchmark ran under that make its numbers vary: the CPU it was pinned to, the
+/// frequency scaling governor and the system load. Checked before `cargo time` and stored with the timings.
+use std::collections::HashMap;
+use std::{fs, io, thread};
+
+use tinyjson::JsonValue;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchConditions {
+    /// Core the solutions were pinned to, `None` if the OS scheduled them freely.
+    pub cpu: Option<usize>,
+    /// Frequency scaling governor of that core (or the first one), e.g. `performance`.
+    pub governor: Option<String>,
+    /// Load average of the last minute.
+    pub load: Option<f64>,
+}
+
+impl BenchConditions {
+    /// Reads the current conditions from `/sys` and `/proc`. Anything unavailable is left out.
+    pub fn detect(cpu: Option<usize>) -> Self {
+        let governor = fs::read_to_string(format!(
+            "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor",
+            cpu.unwrap_or(0)
+        ))
+        .ok()
+        .map(|g| g.trim().to_string());
+
+        let load = fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|l| l.split_whitespace().next()?.parse().ok());
+
+        Self {
+            cpu,
+            governor,
+            load,
+        }
+    }
+
+    /// Reasons the timings might not be stable, empty if there are none.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+
+        if let Some(governor) = self.governor.as_deref().filter(|g| *g != "performance") {
+            warnings.push(format!(
+                "the CPU frequency scaling governor is `{governor}`, use `performance` for stable timings."
+            ));
+        }
+
+        let cores = thread::available_parallelism().map_or(1, usize::from);
+        #[allow(clippy::cast_precision_loss)]
+        let max_load = (cores as f64 / 4.0).max(1.0);
+        if let Some(load) = self.load.filter(|l| *l > max_load) {
+            warnings.push(format!(
+                "the system load is {load:.2}, other processes may slow down the solutions."
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Pins the calling thread to `cpu`.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> io::Result<()> {
+    // SAFETY: `cpu_set_t` is a plain bit mask, all zeros is a valid empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if cpu >= 8 * size_of::<libc::cpu_set_t>() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("CPU {cpu} is out of range"),
+        ));
+    }
+
+    // SAFETY: `set` is a valid `cpu_set_t`, `cpu` was checked to fit in it, and it lives for the
+    // duration of the calls.
+    let result = unsafe {
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &raw const set)
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Pins the calling thread to `cpu`.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU pinning is only supported on Linux",
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchConditions> for JsonValue {
+    fn from(value: &BenchConditions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "cpu".into(),
+            value
+                .cpu
+                .map_or(JsonValue::Null, |c| JsonValue::Number(c as f64)),
+        );
+        map.insert(
+            "governor".into(),
+            value
+                .governor
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "load".into(),
+            value.load.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchConditions {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let map = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.conditions to be a JSON object.")?;
+        let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Self {
+            cpu: number("cpu").map(|c| c as usize),
+            governor: map.get("governor").and_then(|v| v.get::<String>()).cloned(),
+            load: number("load"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    #[cfg(target_os = "linux")]
+    use super::pin_to_cpu;
+    use super::BenchConditions;
+
+    #[test]
+    fn warns_about_unstable_conditions() {
+        let conditions = BenchConditions {
+            cpu: Some(2),
+            governor: Some("powersave".into()),
+            load: Some(1e+6),
+        };
+        let warnings = conditions.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("`powersave`"));
+        assert!(warnings[1].contains("1000000.00"));
+
+        let stable = BenchConditions {
+            governor: Some("performance".into()),
+            load: Some(0.1),
+            ..Default::default()
+        };
+        assert!(stable.warnings().is_empty());
+    }
+
+    #[test]
+    fn roundtrips_conditions() {
+        let conditions = BenchConditions {
+            cpu: Some(3),
+            governor: None,
+            load: Some(0.25),
+        };
+        let json = JsonValue::from(&conditions);
+        assert_eq!(BenchConditions::try_from(&json), Ok(conditions));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn rejects_cpus_out_of_the_set() {
+        let error = pin_to_cpu(1 << 20).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,175 @@
+/// Module that renders benchmark timings as an SVG bar chart, referenced from the README.
+/// Bars use a log scale so that fast days stay visible next to the slow ones.
+use std::fmt::Write as _;
+use std::{fs, io};
+
+use crate::template::timings::{PartTiming, Timings};
+
+pub static CHART_PATH: &str = "./.assets/benchmarks.svg";
+
+const PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+const WIDTH: f64 = 800.0;
+const LABEL_WIDTH: f64 = 70.0;
+const VALUE_WIDTH: f64 = 90.0;
+const BAR_HEIGHT: f64 = 8.0;
+const ROW_HEIGHT: f64 = 2.0 * BAR_HEIGHT + 6.0;
+const HEADER_HEIGHT: f64 = 30.0;
+const FOOTER_HEIGHT: f64 = 30.0;
+
+/// Writes the chart of `timings` to [`CHART_PATH`].
+pub fn write(timings: &Timings) -> io::Result<()> {
+    fs::write(CHART_PATH, render(timings))
+}
+
+/// Renders `timings` as an SVG document, one pair of bars per day.
+pub fn render(timings: &Timings) -> String {
+    let nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_1, t.part_2])
+        .flatten()
+        .map(|p| p.nanos.max(1.0));
+    let (min, max) = nanos.fold((f64::MAX, f64::MIN), |(lo, hi), n| (lo.min(n), hi.max(n)));
+    let (lo, hi) = if min > max {
+        (0, 1)
+    } else {
+        #[allow(clippy::cast_possible_truncation)]
+        let (lo, hi) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+        (lo, hi.max(lo + 1))
+    };
+
+    let plot_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let x = |nanos: f64| {
+        let fraction = (nanos.max(1.0).log10() - f64::from(lo)) / f64::from(hi - lo);
+        LABEL_WIDTH + fraction.clamp(0.0, 1.0) * plot_width
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = timings.data.len() as f64 * ROW_HEIGHT;
+    let height = HEADER_HEIGHT + plot_height + FOOTER_HEIGHT;
+
+    let mut s = String::new();
+    let _ = writeln!(
+        s,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        s,
+        r#"<rect width="{WIDTH}" height="{height}" fill="white"/>"#
+    );
+
+    for (part, color) in PART_COLORS.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let legend_x = LABEL_WIDTH + part as f64 * 80.0;
+        let _ = writeln!(
+            s,
+            r#"<rect x="{legend_x}" y="10" width="10" height="10" fill="{color}"/><text x="{}" y="19">Part {}</text>"#,
+            legend_x + 14.0,
+            part + 1
+        );
+    }
+
+    // one vertical grid line per power of ten.
+    for exponent in lo..=hi {
+        let gx = x(10_f64.powi(exponent));
+        let _ = writeln!(
+            s,
+            r##"<line x1="{gx:.1}" y1="{HEADER_HEIGHT}" x2="{gx:.1}" y2="{}" stroke="#ddd"/><text x="{gx:.1}" y="{}" text-anchor="middle" fill="#555">{}</text>"##,
+            HEADER_HEIGHT + plot_height,
+            HEADER_HEIGHT + plot_height + 15.0,
+            power_label(exponent)
+        );
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = HEADER_HEIGHT + row as f64 * ROW_HEIGHT;
+        let _ = writeln!(
+            s,
+            r#"<text x="{}" y="{}" text-anchor="end">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            y + BAR_HEIGHT + 3.0,
+            timing.day.into_inner()
+        );
+
+        let parts: [Option<PartTiming>; 2] = [timing.part_1, timing.part_2];
+        for (part, value) in parts.iter().enumerate() {
+            let Some(value) = value else {
+                continue;
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = y + part as f64 * BAR_HEIGHT;
+            let bar_width = (x(value.nanos) - LABEL_WIDTH).max(1.0);
+            let _ = writeln!(
+                s,
+                r##"<rect x="{LABEL_WIDTH}" y="{bar_y}" width="{bar_width:.1}" height="{}" fill="{}"><title>Day {} part {}: {value}</title></rect><text x="{:.1}" y="{}" font-size="8" fill="#333">{value}</text>"##,
+                BAR_HEIGHT - 1.0,
+                PART_COLORS[part],
+                timing.day.into_inner(),
+                part + 1,
+                LABEL_WIDTH + bar_width + 4.0,
+                bar_y + BAR_HEIGHT - 1.5,
+            );
+        }
+    }
+
+    s.push_str("</svg>\n");
+    s
+}
+
+/// Label of a power of ten nanoseconds, e.g. `100µs`.
+fn power_label(exponent: i32) -> String {
+    let unit = ["ns", "µs", "ms", "s"][(exponent.clamp(0, 9) / 3) as usize];
+    let exponent = if exponent >= 9 {
+        exponent - 9
+    } else {
+        exponent.max(0) % 3
+    };
+    format!("{}{unit}", 10_u64.pow(exponent.unsigned_abs()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{power_label, render};
+    use crate::day;
+    use crate::template::timings::{mock_timings, Timings};
+    use crate::template::{Day, SEASON_LENGTH};
+
+    fn get_mock_timings() -> Timings {
+        mock_timings(&[
+            (day!(6), Some("7.9s"), Some("3.1ms")),
+            (Day::new(SEASON_LENGTH).unwrap(), Some("420ns"), None),
+        ])
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let svg = render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("Day 6 part 1: 7.9s"));
+        assert!(svg.contains(&format!("Day {SEASON_LENGTH} part 1: 420.0ns")));
+        // grid spans 100ns to 10s.
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">10s</text>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert!(svg.contains("</svg>"));
+        assert!(!svg.contains("<title>"));
+    }
+
+    #[test]
+    fn labels_powers_of_ten() {
+        assert_eq!(power_label(0), "1ns");
+        assert_eq!(power_label(5), "100µs");
+        assert_eq!(power_label(6), "1ms");
+        assert_eq!(power_label(10), "10s");
+    }
+}
@@ -0,0 +1,124 @@
+/// Build settings solutions can be benchmarked with, see `cargo time --profiles`.
+use std::fmt::Display;
+use std::process::Command;
+use std::str::FromStr;
+
+/// A cargo profile, optionally built with extra `RUSTFLAGS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildProfile {
+    /// Name shown in reports, e.g. `native`.
+    pub name: String,
+    /// Profile passed to `cargo --profile`, defined in `Cargo.toml` unless built-in.
+    pub cargo_profile: String,
+    /// Extra flags for rustc. Such builds go to their own target dir so they don't invalidate others.
+    pub rustflags: Option<&'static str>,
+}
+
+impl BuildProfile {
+    fn new(name: &str, cargo_profile: &str, rustflags: Option<&'static str>) -> Self {
+        Self {
+            name: name.into(),
+            cargo_profile: cargo_profile.into(),
+            rustflags,
+        }
+    }
+
+    pub fn dev() -> Self {
+        Self::new("dev", "dev", None)
+    }
+
+    pub fn release() -> Self {
+        Self::new("release", "release", None)
+    }
+
+    /// Release build with frame pointers, which the profiler of `cargo solve --flamegraph` walks.
+    pub fn profiling() -> Self {
+        Self::new("profiling", "release", Some("-C force-frame-pointers=yes"))
+    }
+
+    /// Parses a comma-separated list like `release,lto,native`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        let profiles: Vec<Self> = s
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(Self::from_str)
+            .collect::<Result<_, _>>()?;
+
+        if profiles.is_empty() {
+            return Err("expected at least one profile.".into());
+        }
+        Ok(profiles)
+    }
+
+    /// Sets up `cmd`, a `cargo` invocation, to build with this profile.
+    pub fn apply(&self, cmd: &mut Command) {
+        cmd.args(["--profile", &self.cargo_profile]);
+
+        if let Some(flags) = self.rustflags {
+            let rustflags = match std::env::var("RUSTFLAGS") {
+                Ok(existing) if !existing.is_empty() => format!("{existing} {flags}"),
+                _ => flags.to_string(),
+            };
+            cmd.env("RUSTFLAGS", rustflags)
+                .env("CARGO_TARGET_DIR", format!("target/{}", self.name));
+        }
+    }
+}
+
+impl FromStr for BuildProfile {
+    type Err = String;
+
+    /// Known names are `release`, `lto`, `native` and `abort`.
+    /// Any other name is taken as a profile defined in `Cargo.toml`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("empty profile name.".into()),
+            "native" => Ok(Self::new(s, "release", Some("-C target-cpu=native"))),
+            _ if s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                Ok(Self::new(s, s, None))
+            }
+            _ => Err(format!("invalid profile name `{s}`.")),
+        }
+    }
+}
+
+impl Display for BuildProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BuildProfile;
+    use std::process::Command;
+
+    #[test]
+    fn parses_profile_lists() {
+        let profiles = BuildProfile::parse_list("release, lto,native").unwrap();
+        assert_eq!(profiles.len(), 3);
+        assert_eq!(profiles[1].cargo_profile, "lto");
+        assert_eq!(profiles[2].cargo_profile, "release");
+        assert!(profiles[2].rustflags.is_some());
+
+        assert!(BuildProfile::parse_list(",").is_err());
+        assert!(BuildProfile::parse_list("release,--foo bar").is_err());
+    }
+
+    #[test]
+    fn applies_profiles() {
+        let mut cmd = Command::new("cargo");
+        "native".parse::<BuildProfile>().unwrap().apply(&mut cmd);
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--profile", "release"]);
+        assert!(cmd
+            .get_envs()
+            .any(|(k, v)| k == "CARGO_TARGET_DIR" && v == Some("target/native".as_ref())));
+    }
+}
@@ -0,0 +1,721 @@
+/// Description of the command-line interface: commands, their arguments and flags.
+/// Drives `--help` output, argument validation and shell completions.
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+/// A sub-command such as `solve`.
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub args: &'static [Arg],
+    pub flags: &'static [Flag],
+    /// Groups of flags selecting different modes, at most one flag of each group may be given.
+    pub exclusive: &'static [&'static [&'static str]],
+    /// `(flag, other)`: `flag` has no effect without `other`, so it's rejected alone.
+    pub requires: &'static [(&'static str, &'static str)],
+}
+
+/// A positional argument.
+#[derive(Debug)]
+pub struct Arg {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+    pub kind: ArgKind,
+}
+
+/// What a positional argument holds, used to offer completions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    Day,
+    DaySet,
+    Shell,
+}
+
+/// A `--flag`, optionally taking a value.
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+const DAY: Arg = Arg {
+    name: "DAY",
+    help: "Day number, e.g. `6`",
+    required: true,
+    kind: ArgKind::Day,
+};
+
+const DAYS_HELP: &str = "Days to select, e.g. `1-5,7,12-`, `all`, `unsolved` or `slowest:5`";
+
+const RELEASE: Flag = Flag {
+    name: "--release",
+    value: None,
+    help: "Build in release mode",
+};
+
+const DOWNLOAD: Command = Command {
+    name: "download",
+    summary: "Download puzzle descriptions and inputs with aoc-cli",
+    args: &[Arg {
+        name: "DAYS",
+        help: DAYS_HELP,
+        required: true,
+        kind: ArgKind::DaySet,
+    }],
+    flags: &[],
+    exclusive: &[],
+    requires: &[],
+};
+
+const READ: Command = Command {
+    name: "read",
+    summary: "Print the puzzle description of a day",
+    args: &[DAY],
+    flags: &[],
+    exclusive: &[],
+    requires: &[],
+};
+
+const SCAFFOLD: Command = Command {
+    name: "scaffold",
+    summary: "Create the solution, input and example files of a day",
+    args: &[DAY],
+    flags: &[
+        Flag {
+            name: "--download",
+            value: None,
+            help: "Also download the puzzle",
+        },
+        Flag {
+            name: "--overwrite",
+            value: None,
+            help: "Replace an existing solution file",
+        },
+    ],
+    exclusive: &[],
+    requires: &[],
+};
+
+const SOLVE: Command = Command {
+    name: "solve",
+    summary: "Run the solution of a day",
+    args: &[DAY],
+    flags: &[
+        RELEASE,
+        Flag {
+            name: "--dhat",
+            value: None,
+            help: "Profile heap allocations with dhat",
+        },
+        Flag {
+            name: "--submit",
+            value: Some("PART"),
+            help: "Submit the answer of part 1 or 2 with aoc-cli",
+        },
+        Flag {
+            name: "--part",
+            value: Some("PART"),
+            help: "Only run part 1 or 2",
+        },
+        Flag {
+            name: "--input",
+            value: Some("FILE"),
+            help: "Read the input from FILE, or stdin with `-`",
+        },
+        Flag {
+            name: "--example",
+            value: None,
+            help: "Read the input from the example file",
+        },
+        Flag {
+            name: "--flamegraph",
+            value: None,
+            help: "Profile the solution and write a flame graph to data/profiles/",
+        },
+        Flag {
+            name: "--check-determinism",
+            value: Some("RUNS"),
+            help: "Run each part RUNS times and fail if the answers differ",
+        },
+        Flag {
+            name: "--random-seed",
+            value: None,
+            help: "Reseed the fast hash maps before each run of the determinism check",
+        },
+        Flag {
+            name: "--variants",
+            value: None,
+            help: "Run and benchmark every implementation of the parts",
+        },
+    ],
+    exclusive: &[],
+    requires: &[],
+};
+
+const SHRINK: Command = Command {
+    name: "shrink",
+    summary: "Minimize an input a part answers wrong, compared with a variant or an answer",
+    args: &[DAY],
+    flags: &[
+        Flag {
+            name: "--part",
+            value: Some("PART"),
+            help: "Part to check, 1 or 2 (required)",
+        },
+        Flag {
+            name: "--oracle",
+            value: Some("VARIANT"),
+            help: "Variant of the part trusted to be correct",
+        },
+        Flag {
+            name: "--expect",
+            value: Some("ANSWER"),
+            help: "Answer the part should give, instead of `--oracle`",
+        },
+        Flag {
+            name: "--input",
+            value: Some("FILE"),
+            help: "Shrink FILE instead of the puzzle input",
+        },
+    ],
+    exclusive: &[&["--oracle", "--expect"]],
+    requires: &[],
+};
+
+const ALL: Command = Command {
+    name: "all",
+    summary: "Run the solutions of several days",
+    args: &[Arg {
+        name: "DAYS",
+        help: DAYS_HELP,
+        required: false,
+        kind: ArgKind::DaySet,
+    }],
+    flags: &[RELEASE],
+    exclusive: &[],
+    requires: &[],
+};
+
+const TIME: Command = Command {
+    name: "time",
+    summary: "Benchmark solutions",
+    args: &[Arg {
+        name: "DAYS",
+        help: DAYS_HELP,
+        required: false,
+        kind: ArgKind::DaySet,
+    }],
+    flags: &[
+        Flag {
+            name: "--all",
+            value: None,
+            help: "Benchmark every day, including already benchmarked ones",
+        },
+        Flag {
+            name: "--store",
+            value: None,
+            help: "Save timings and update the README",
+        },
+        Flag {
+            name: "--policy",
+            value: Some("POLICY"),
+            help: "Timing kept on --store: `latest`, `best` or `median:N`",
+        },
+        Flag {
+            name: "--pin-cpu",
+            value: Some("CPU"),
+            help: "Pin the solutions to a CPU core while benchmarking (Linux only)",
+        },
+        Flag {
+            name: "--profiles",
+            value: Some("PROFILES"),
+            help: "Compare build profiles, e.g. `release,lto,native`, instead of storing timings",
+        },
+        Flag {
+            name: "--history",
+            value: Some("DAY"),
+            help: "Show the stored timings of a day over time instead of running",
+        },
+        Flag {
+            name: "--export",
+            value: Some("FORMAT"),
+            help: "Print the stored timings as `csv`, `json` or `md` instead of running",
+        },
+        Flag {
+            name: "--compare-with",
+            value: Some("FILE"),
+            help: "Compare the stored timings with another timings.json instead of running",
+        },
+    ],
+    exclusive: &[
+        // the modes that don't store, and the ones that don't run.
+        &[
+            "--store",
+            "--profiles",
+            "--history",
+            "--export",
+            "--compare-with",
+        ],
+        &[
+            "--all",
+            "--profiles",
+            "--history",
+            "--export",
+            "--compare-with",
+        ],
+        &["--pin-cpu", "--history", "--export", "--compare-with"],
+    ],
+    requires: &[("--policy", "--store")],
+};
+
+const STATUS: Command = Command {
+    name: "status",
+    summary: "Show the progress of every day",
+    args: &[Arg {
+        name: "DAYS",
+        help: DAYS_HELP,
+        required: false,
+        kind: ArgKind::DaySet,
+    }],
+    flags: &[
+        Flag {
+            name: "--run",
+            value: None,
+            help: "Also run the example tests and the solutions",
+        },
+        Flag {
+            name: "--timeout",
+            value: Some("SECONDS"),
+            help: "Time a day may run for with `--run` (default: 60)",
+        },
+    ],
+    exclusive: &[],
+    requires: &[("--timeout", "--run")],
+};
+
+#[cfg(feature = "today")]
+const TODAY: Command = Command {
+    name: "today",
+    summary: "Scaffold, download and read the puzzle of the day",
+    args: &[],
+    flags: &[Flag {
+        name: "--wait",
+        value: None,
+        help: "Wait for the next puzzle to unlock first",
+    }],
+    exclusive: &[],
+    requires: &[],
+};
+
+#[cfg(feature = "today")]
+const COUNTDOWN: Command = Command {
+    name: "countdown",
+    summary: "Show the time left until the next puzzle unlocks",
+    args: &[],
+    flags: &[],
+    exclusive: &[],
+    requires: &[],
+};
+
+const COMPLETIONS: Command = Command {
+    name: "completions",
+    summary: "Print a completion script for the `cargo` aliases",
+    args: &[Arg {
+        name: "SHELL",
+        help: "One of `bash`, `zsh` or `fish`",
+        required: true,
+        kind: ArgKind::Shell,
+    }],
+    flags: &[],
+    exclusive: &[],
+    requires: &[],
+};
+
+/// Every available command.
+pub fn commands() -> Vec<&'static Command> {
+    vec![
+        &DOWNLOAD,
+        &READ,
+        &SCAFFOLD,
+        &SOLVE,
+        &SHRINK,
+        &ALL,
+        &TIME,
+        &STATUS,
+        #[cfg(feature = "today")]
+        &TODAY,
+        #[cfg(feature = "today")]
+        &COUNTDOWN,
+        &COMPLETIONS,
+    ]
+}
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    commands().into_iter().find(|c| c.name == name)
+}
+
+pub fn version() -> String {
+    format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+}
+
+/// Top-level help, listing every command.
+pub fn help() -> String {
+    let commands = commands();
+    let width = commands.iter().map(|c| c.name.len()).max().unwrap_or(0);
+
+    let mut s = String::new();
+    let _ = writeln!(s, "Usage: cargo <COMMAND> [ARGS]\n\nCommands:");
+    for command in commands {
+        let _ = writeln!(s, "  {:width$}  {}", command.name, command.summary);
+    }
+    let _ = write!(
+        s,
+        "\nOptions:\n  -h, --help     Print help\n  -V, --version  Print version\n\n\
+        See `cargo <COMMAND> --help` for details on a command."
+    );
+    s
+}
+
+/// Help of a single command.
+pub fn usage(command: &Command) -> String {
+    let mut s = format!("{}\n\nUsage: cargo {}", command.summary, command.name);
+    for arg in command.args {
+        if arg.required {
+            let _ = write!(s, " <{}>", arg.name);
+        } else {
+            let _ = write!(s, " [{}]", arg.name);
+        }
+    }
+    let _ = writeln!(s, " [OPTIONS]");
+
+    if !command.args.is_empty() {
+        let _ = writeln!(s, "\nArguments:");
+        for arg in command.args {
+            let _ = writeln!(s, "  <{}>  {}", arg.name, arg.help);
+        }
+    }
+
+    let flags: Vec<(String, &str)> = command
+        .flags
+        .iter()
+        .map(|f| match f.value {
+            Some(value) => (format!("{} <{value}>", f.name), f.help),
+            None => (f.name.to_string(), f.help),
+        })
+        .chain([("-h, --help".to_string(), "Print help")])
+        .collect();
+    let width = flags.iter().map(|(f, _)| f.len()).max().unwrap_or(0);
+
+    let _ = writeln!(s, "\nOptions:");
+    for (flag, help) in flags {
+        let _ = writeln!(s, "  {flag:width$}  {help}");
+    }
+    s.trim_end().to_string()
+}
+
+/// The flags and positional arguments given to a command, checked against its description.
+#[derive(Debug)]
+pub struct Parsed {
+    command: &'static Command,
+    flags: Vec<(&'static str, Option<String>)>,
+    args: Vec<String>,
+    /// Flags and arguments asked for, see [`Parsed::finish`].
+    read: Vec<&'static str>,
+}
+
+/// Parses the arguments following the name of `command`. A flag's value follows it, either as the
+/// next argument or after a `=`, e.g. `--policy=best`.
+pub fn parse(command: &'static Command, args: &[String]) -> Result<Parsed, String> {
+    let mut flags: Vec<(&'static str, Option<String>)> = vec![];
+    let mut positional = vec![];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg.clone());
+            continue;
+        }
+
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let flag = command
+            .flags
+            .iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| format!("unexpected argument `{name}`."))?;
+        if flags.iter().any(|(f, _)| *f == flag.name) {
+            return Err(format!("`{name}` was given more than once."));
+        }
+
+        let value = match (flag.value, inline) {
+            (None, None) => None,
+            (None, Some(_)) => return Err(format!("`{name}` doesn't take a value.")),
+            (Some(_), Some(value)) => Some(value),
+            (Some(value), None) => Some(
+                args.next()
+                    .ok_or_else(|| format!("`{name}` expects a value <{value}>."))?
+                    .clone(),
+            ),
+        };
+        flags.push((flag.name, value));
+    }
+
+    if let Some(extra) = positional
+        .get(command.args.len()..)
+        .filter(|e| !e.is_empty())
+    {
+        return Err(format!("unexpected argument(s): {extra:?}."));
+    }
+    if let Some(arg) = command.args[positional.len()..].iter().find(|a| a.required) {
+        return Err(format!("missing argument <{}>.", arg.name));
+    }
+
+    let given = |name: &str| flags.iter().any(|(f, _)| *f == name);
+    for (flag, other) in command.requires {
+        if given(flag) && !given(other) {
+            return Err(format!("`{flag}` requires `{other}`."));
+        }
+    }
+    for group in command.exclusive {
+        let conflicting: Vec<&str> = group.iter().copied().filter(|f| given(f)).collect();
+        if conflicting.len() > 1 {
+            return Err(format!("{} are exclusive.", enumerate(&conflicting)));
+        }
+    }
+
+    Ok(Parsed {
+        command,
+        flags,
+        args: positional,
+        read: vec![],
+    })
+}
+
+/// Formats `["--a", "--b", "--c"]` as ``"`--a`, `--b` and `--c`"``.
+fn enumerate(flags: &[&str]) -> String {
+    let quoted: Vec<String> = flags.iter().map(|f| format!("`{f}`")).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => quoted.concat(),
+    }
+}
+
+impl Parsed {
+    /// Whether the flag `name` was given.
+    pub fn contains(&mut self, name: &'static str) -> bool {
+        self.lookup_flag(name).is_some()
+    }
+
+    /// Parses the value of the flag `name` if it was given.
+    pub fn value<T: FromStr>(&mut self, name: &'static str) -> Result<Option<T>, String>
+    where
+        T::Err: Display,
+    {
+        self.value_from_fn(name, str::parse)
+    }
+
+    /// Parses the value of the flag `name` with `f` if it was given.
+    pub fn value_from_fn<T, E: Display>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<Option<T>, String> {
+        self.lookup_flag(name)
+            .flatten()
+            .map(|value| {
+                f(&value).map_err(|e| format!("invalid value `{value}` for `{name}`: {e}"))
+            })
+            .transpose()
+    }
+
+    /// Parses the value of the flag `name`, which must be given.
+    pub fn required_value<T, E: Display>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<T, String> {
+        self.value_from_fn(name, f)?
+            .ok_or_else(|| format!("`{name}` is required."))
+    }
+
+    /// Parses the positional argument `name` if it was given.
+    pub fn arg<T: FromStr>(&mut self, name: &'static str) -> Result<Option<T>, String>
+    where
+        T::Err: Display,
+    {
+        let index = self
+            .command
+            .args
+            .iter()
+            .position(|a| a.name == name)
+            .unwrap_or_else(|| panic!("`{name}` is not an argument of `{}`", self.command.name));
+        self.read.push(name);
+        self.args
+            .get(index)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| format!("invalid <{name}> `{value}`: {e}"))
+            })
+            .transpose()
+    }
+
+    /// Parses the positional argument `name`, which must be given.
+    pub fn required_arg<T: FromStr>(&mut self, name: &'static str) -> Result<T, String>
+    where
+        T::Err: Display,
+    {
+        self.arg(name)?
+            .ok_or_else(|| format!("missing argument <{name}>."))
+    }
+
+    /// Checks that every flag and argument of the command was asked for, so that none is accepted
+    /// and then silently ignored.
+    ///
+    /// # Panics
+    /// Panics if one was never asked for, which is a bug of the caller.
+    pub fn finish(self) {
+        let described = self.command.flags.iter().map(|f| f.name);
+        for name in described.chain(self.command.args.iter().map(|a| a.name)) {
+            assert!(
+                self.read.contains(&name),
+                "`{name}` of `{}` is described but never read",
+                self.command.name
+            );
+        }
+    }
+
+    /// `None` if the flag wasn't given, `Some(None)` if it was and takes no value.
+    fn lookup_flag(&mut self, name: &'static str) -> Option<Option<String>> {
+        assert!(
+            self.command.flags.iter().any(|f| f.name == name),
+            "`{name}` is not a flag of `{}`",
+            self.command.name
+        );
+        self.read.push(name);
+        self.flags
+            .iter()
+            .find(|(f, _)| *f == name)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+/// Parses the value of a `--submit`-like flag.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("expecting part `1` or `2`, got `{s}`")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{commands, find_command, help, parse, parse_part, usage, Parsed};
+
+    #[test]
+    fn lists_every_command() {
+        let help = help();
+        for command in commands() {
+            assert!(help.contains(command.name));
+        }
+    }
+
+    #[test]
+    fn formats_command_usage() {
+        let usage = usage(find_command("solve").unwrap());
+        assert!(usage.contains("Usage: cargo solve <DAY> [OPTIONS]"));
+        assert!(usage.contains("--check-determinism <RUNS>  Run each part"));
+        assert!(usage.contains("--submit <PART>"));
+        assert!(usage.contains("-h, --help"));
+    }
+
+    fn parse_args(name: &str, args: &str) -> Result<Parsed, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse(find_command(name).unwrap(), &args)
+    }
+
+    #[test]
+    fn parses_flags_and_arguments() {
+        let mut parsed = parse_args("time", "--store 3-5 --policy=best --pin-cpu 2").unwrap();
+        assert!(parsed.contains("--store"));
+        assert!(!parsed.contains("--all"));
+        assert_eq!(parsed.value::<String>("--policy"), Ok(Some("best".into())));
+        assert_eq!(parsed.value::<usize>("--pin-cpu"), Ok(Some(2)));
+        assert_eq!(parsed.value::<String>("--export"), Ok(None));
+        assert_eq!(parsed.arg::<String>("DAYS"), Ok(Some("3-5".into())));
+
+        let mut parsed = parse_args("shrink", "6 --part 3").unwrap();
+        assert!(parsed.required_value("--part", parse_part).is_err());
+        assert!(parsed
+            .required_value("--input", str::parse::<String>)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse_args("time", "--unknown").is_err());
+        assert!(parse_args("time", "--store --store").is_err());
+        assert!(parse_args("time", "--store=yes").is_err());
+        assert!(parse_args("time", "--policy").is_err());
+        assert!(parse_args("time", "1-3 5").is_err());
+        assert!(parse_args("solve", "--release").is_err());
+        assert!(parse_args("read", "--help").is_err());
+    }
+
+    #[test]
+    fn rejects_exclusive_and_incomplete_flags() {
+        assert!(parse_args("time", "--store --export csv").is_err());
+        assert!(parse_args("time", "--history 3 --compare-with other.json").is_err());
+        assert!(parse_args("time", "--all --profiles release,lto").is_err());
+        assert!(parse_args("time", "--policy best").is_err());
+        assert!(parse_args("time", "--profiles release,lto --pin-cpu 1").is_ok());
+        assert!(parse_args("time", "--all --store --policy best --pin-cpu 1").is_ok());
+        assert!(parse_args("shrink", "6 --part 1 --oracle naive --expect 12").is_err());
+        assert!(parse_args("status", "--timeout 5").is_err());
+        assert!(parse_args("status", "--run --timeout 5").is_ok());
+    }
+
+    #[test]
+    fn rules_refer_to_described_flags() {
+        for command in commands() {
+            let rules = command.exclusive.iter().flat_map(|g| g.iter());
+            let rules = rules.chain(command.requires.iter().flat_map(|(a, b)| [a, b]));
+            for name in rules {
+                assert!(
+                    command.flags.iter().any(|f| f.name == *name),
+                    "`{name}` of `{}` is not described",
+                    command.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is described but never read")]
+    fn panics_if_a_flag_is_never_read() {
+        let mut parsed = parse_args("scaffold", "6 --download").unwrap();
+        assert!(parsed.contains("--download"));
+        let _: Option<String> = parsed.arg("DAY").unwrap();
+        parsed.finish();
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a flag of `read`")]
+    fn panics_if_an_undescribed_flag_is_read() {
+        parse_args("read", "6").unwrap().contains("--release");
+    }
+
+    #[test]
+    fn validates_parts() {
+        assert_eq!(parse_part("2"), Ok(2));
+        assert!(parse_part("3").is_err());
+    }
+}
@@ -1,5 +1,10 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{build_profile::BuildProfile, run_multi::run_multi, DaySet};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: &DaySet, is_release: bool) {
+    let profile = if is_release {
+        BuildProfile::release()
+    } else {
+        BuildProfile::dev()
+    };
+    run_multi(&days.days(), &profile, false, None);
 }
@@ -0,0 +1,257 @@
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+use crate::template::cli::{commands, ArgKind, Command};
+
+/// Shells we can generate completion scripts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!(
+                "unsupported shell `{s}`, expecting `bash`, `zsh` or `fish`"
+            )),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        })
+    }
+}
+
+pub fn handle(shell: Shell) {
+    print!("{}", generate(shell));
+}
+
+/// Generates a completion script for the `cargo` aliases of the commands, e.g. `cargo solve`.
+/// The scripts extend the completion of `cargo` itself rather than replace it:
+/// - bash: `cargo` forwards `cargo solve` to the completion of `cargo-solve`, with bash-completion
+///   2.12 or later.
+/// - zsh: `cargo` calls a `_cargo-solve` function if it's defined.
+/// - fish: completions of `cargo` add up.
+///
+/// Day numbers are looked up in `src/bin/` when completing, so new days show up without regenerating.
+pub fn generate(shell: Shell) -> String {
+    let commands = commands();
+    let prefix = ident(env!("CARGO_PKG_NAME"));
+    let load = match shell {
+        Shell::Bash | Shell::Zsh => format!("source <(cargo completions {shell})"),
+        Shell::Fish => "cargo completions fish | source".into(),
+    };
+    let mut s = format!(
+        "# Completions of the `cargo` aliases of {}, load with:\n#   {load}\n",
+        env!("CARGO_PKG_NAME")
+    );
+    s += &match shell {
+        Shell::Bash => bash(&commands, &prefix),
+        Shell::Zsh => zsh(&commands),
+        Shell::Fish => fish(&commands),
+    };
+    s
+}
+
+/// Quotes `s` as a single shell word, with the escapes of single quotes `shell` supports.
+fn quote(shell: Shell, s: &str) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("'{}'", s.replace('\'', r"'\''")),
+        Shell::Fish => format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'")),
+    }
+}
+
+fn ident(name: &str) -> String {
+    name.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+}
+
+fn positional_kind(command: &Command) -> Option<ArgKind> {
+    command.args.first().map(|a| a.kind)
+}
+
+fn values_for(kind: ArgKind) -> &'static str {
+    match kind {
+        ArgKind::Day => "",
+        ArgKind::DaySet => "all unsolved",
+        ArgKind::Shell => "bash zsh fish",
+    }
+}
+
+fn bash(commands: &[&Command], prefix: &str) -> String {
+    let f = prefix;
+    let names: Vec<String> = commands
+        .iter()
+        .map(|c| format!("cargo-{}", c.name))
+        .collect();
+
+    let mut s = String::new();
+    let _ = writeln!(s, "_{f}_days() {{");
+    let _ = writeln!(
+        s,
+        "    ls src/bin 2>/dev/null | sed -n 's/^\\([0-9][0-9]*\\)\\.rs$/\\1/p'"
+    );
+    let _ = writeln!(s, "}}\n");
+    let _ = writeln!(s, "_{f}() {{");
+    let _ = writeln!(s, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"");
+    let _ = writeln!(s, "    local words=\"--help\"");
+    let _ = writeln!(s, "    case \"${{COMP_WORDS[0]#cargo-}}\" in");
+    for command in commands {
+        let mut words: Vec<&str> = command.flags.iter().map(|fl| fl.name).collect();
+        let kind = positional_kind(command);
+        if let Some(kind) = kind {
+            words.push(values_for(kind));
+        }
+        let _ = write!(
+            s,
+            "        {})\n            words=\"$words {}",
+            command.name,
+            words.join(" ")
+        );
+        if matches!(kind, Some(ArgKind::Day | ArgKind::DaySet)) {
+            let _ = write!(s, " $(_{f}_days)");
+        }
+        let _ = writeln!(s, "\"\n            ;;");
+    }
+    let _ = writeln!(s, "    esac");
+    let _ = writeln!(s, "    COMPREPLY=($(compgen -W \"$words\" -- \"$cur\"))");
+    let _ = writeln!(s, "}}\n");
+    let _ = writeln!(s, "complete -F _{f} {}", names.join(" "));
+    s
+}
+
+fn zsh(commands: &[&Command]) -> String {
+    let mut s = String::new();
+    for command in commands {
+        let _ = writeln!(s, "\n_cargo-{}() {{", command.name);
+        let _ = writeln!(s, "    local -a flags values");
+        let _ = writeln!(s, "    flags=('--help:Print help')");
+        for flag in command.flags {
+            let entry = format!("{}:{}", flag.name, flag.help);
+            let _ = writeln!(s, "    flags+=({})", quote(Shell::Zsh, &entry));
+        }
+        match positional_kind(command) {
+            Some(kind @ (ArgKind::Day | ArgKind::DaySet)) => {
+                let _ = writeln!(
+                    s,
+                    "    values=(${{${{(M)${{(f)\"$(ls src/bin 2>/dev/null)\"}}:#[0-9]*.rs}}%.rs}} {})",
+                    values_for(kind)
+                );
+            }
+            Some(kind) => {
+                let _ = writeln!(s, "    values=({})", values_for(kind));
+            }
+            None => {}
+        }
+        let _ = writeln!(s, "    _describe 'option' flags");
+        let _ = writeln!(s, "    compadd -a values");
+        let _ = writeln!(s, "}}");
+    }
+    s
+}
+
+fn fish(commands: &[&Command]) -> String {
+    let days = "'(ls src/bin 2>/dev/null | string replace -rf \"^(\\d+)\\.rs\\$\" \"\\$1\")'";
+
+    let mut s = String::new();
+    for command in commands {
+        let _ = writeln!(
+            s,
+            "complete -c cargo -n __fish_use_subcommand -f -a {} -d {}",
+            command.name,
+            quote(Shell::Fish, command.summary)
+        );
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+        for flag in command.flags {
+            let _ = write!(
+                s,
+                "complete -c cargo -n '{condition}' -f -l {} -d {}",
+                flag.name.trim_start_matches("--"),
+                quote(Shell::Fish, flag.help)
+            );
+            if flag.value.is_some() {
+                let _ = write!(s, " -r");
+            }
+            let _ = writeln!(s);
+        }
+        match positional_kind(command) {
+            Some(ArgKind::Day) => {
+                let _ = writeln!(s, "complete -c cargo -n '{condition}' -f -a {days}");
+            }
+            Some(kind @ ArgKind::DaySet) => {
+                let _ = writeln!(s, "complete -c cargo -n '{condition}' -f -a {days}");
+                let _ = writeln!(
+                    s,
+                    "complete -c cargo -n '{condition}' -f -a '{}'",
+                    values_for(kind)
+                );
+            }
+            Some(kind) => {
+                let _ = writeln!(
+                    s,
+                    "complete -c cargo -n '{condition}' -f -a '{}'",
+                    values_for(kind)
+                );
+            }
+            None => {}
+        }
+    }
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, quote, Shell};
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("zsh".parse::<Shell>(), Ok(Shell::Zsh));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn quotes_help_strings() {
+        assert_eq!(quote(Shell::Zsh, "don't"), r"'don'\''t'");
+        assert_eq!(quote(Shell::Fish, r"don't \n"), r"'don\'t \\n'");
+    }
+
+    #[test]
+    fn generates_bash_completions() {
+        let script = generate(Shell::Bash);
+        assert!(script.contains("source <(cargo completions bash)"));
+        assert!(script.contains("complete -F _advent_of_code cargo-download cargo-read"));
+        assert!(script.contains("solve)"));
+        assert!(script.contains("--submit"));
+        assert!(script.contains("$(_advent_of_code_days)"));
+    }
+
+    #[test]
+    fn generates_zsh_completions() {
+        let script = generate(Shell::Zsh);
+        assert!(script.contains("\n_cargo-time() {"));
+        assert!(script.contains("flags+=('--store:"));
+    }
+
+    #[test]
+    fn generates_fish_completions() {
+        let script = generate(Shell::Fish);
+        assert!(script.contains("complete -c cargo -n __fish_use_subcommand -f -a scaffold"));
+        assert!(script.contains("-l overwrite"));
+        assert!(script.contains("-a 'bash zsh fish'"));
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{TimeDelta, Utc};
+
+use crate::template::Day;
+
+pub fn handle() {
+    let now = Utc::now();
+    let (day, unlock) = Day::next_unlock(now);
+    println!(
+        "🎄 Day {} unlocks in {} ({unlock}).",
+        day.into_inner(),
+        format_remaining(unlock.signed_duration_since(now))
+    );
+}
+
+/// Formats a duration as e.g. `2d 03h 04m 05s`, omitting leading zero units.
+pub fn format_remaining(remaining: TimeDelta) -> String {
+    let secs = remaining.num_seconds().max(0);
+    let (days, hours, mins, secs) = (
+        secs / 86_400,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}h {mins:02}m {secs:02}s")
+    } else if hours > 0 {
+        format!("{hours}h {mins:02}m {secs:02}s")
+    } else if mins > 0 {
+        format!("{mins}m {secs:02}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_remaining;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(TimeDelta::seconds(5)), "5s");
+        assert_eq!(format_remaining(TimeDelta::seconds(65)), "1m 05s");
+        assert_eq!(
+            format_remaining(TimeDelta::seconds(3 * 3600 + 7)),
+            "3h 00m 07s"
+        );
+        assert_eq!(
+            format_remaining(TimeDelta::seconds(90_061)),
+            "1d 01h 01m 01s"
+        );
+        assert_eq!(format_remaining(TimeDelta::seconds(-3)), "0s");
+    }
+}
@@ -1,6 +1,13 @@
 pub mod all;
+pub mod completions;
+#[cfg(feature = "today")]
+pub mod countdown;
 pub mod download;
 pub mod read;
 pub mod scaffold;
+pub mod shrink;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -0,0 +1,41 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::runner::Oracle;
+use crate::template::Day;
+
+/// Minimizes the input of `part` while the solution disagrees with `oracle`,
+/// see [`crate::template::runner::shrink_part`].
+pub fn handle(day: Day, part: u8, oracle: &Oracle, input: Option<&str>) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--part".to_string(),
+        part.to_string(),
+    ];
+
+    match oracle {
+        Oracle::Variant(variant) => cmd_args.extend(["--shrink".to_string(), variant.clone()]),
+        Oracle::Answer(answer) => cmd_args.extend(["--shrink-expect".to_string(), answer.clone()]),
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    // e.g. an unknown oracle variant or an input the parts agree on.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
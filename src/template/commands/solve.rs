@@ -1,34 +1,80 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{BuildProfile, Day};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options forwarded to the solution binary, see [`crate::template::runner::RunOptions`].
+#[derive(Debug, Default)]
+pub struct RunArgs {
+    pub part: Option<u8>,
+    pub input: Option<String>,
+    pub example: bool,
+    pub flamegraph: bool,
+    pub check_determinism: Option<u32>,
+    pub random_seed: bool,
+    pub variants: bool,
+}
+
+pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>, run_args: &RunArgs) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", &day.to_string()]);
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+        cmd.args(["--profile", "dhat", "--features", "dhat-heap"]);
+    } else if run_args.flamegraph {
+        BuildProfile::profiling().apply(&mut cmd);
+    } else if release || run_args.variants {
+        // benchmarking a debug build would mostly show what the optimizer removes.
+        cmd.arg("--release");
     }
 
-    cmd_args.push("--".to_string());
+    let mut cmd_args = vec!["--".to_string()];
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(part) = run_args.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(input) = &run_args.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.clone());
+    }
+
+    if run_args.example {
+        cmd_args.push("--example".to_string());
+    }
+
+    if run_args.flamegraph {
+        cmd_args.push("--flamegraph".to_string());
+    }
+
+    if let Some(runs) = run_args.check_determinism {
+        cmd_args.push("--check-determinism".to_string());
+        cmd_args.push(runs.to_string());
+    }
+
+    if run_args.random_seed {
+        cmd_args.push("--random-seed".to_string());
+    }
+
+    if run_args.variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    let mut cmd = cmd
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // e.g. a mismatch between variants or runs, which should fail the calling script.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
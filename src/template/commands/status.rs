@@ -0,0 +1,338 @@
+use std::fmt::Write;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Child, Command, Output, Stdio};
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, DaySet};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How long the example tests and the solution of a day may run together, see `--timeout`.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Result of running the tests or a part of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    TimedOut,
+}
+
+/// What we know about a single day.
+#[derive(Debug)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub has_input: bool,
+    pub has_example: bool,
+    /// `None` if the tests were not run.
+    pub tests_pass: Option<Outcome>,
+    /// Whether each part returned `Some`, `None` if the solution was not run.
+    pub parts: [Option<Outcome>; 2],
+    pub timing: Option<Timing>,
+    /// Whether the timing was measured with other sources or input, see [`Timings::is_day_stale`].
+    pub timing_stale: bool,
+    pub readme_stale: bool,
+}
+
+/// Prints the status of `days`. Example tests and solutions are only run with `run`, which is
+/// the time each day may take for them.
+pub fn handle(days: Option<&DaySet>, run: Option<Duration>) {
+    let timings = Timings::read_from_file_or_exit();
+    let readme = fs::read_to_string("README.md").unwrap_or_default();
+
+    let mut days: Vec<Day> = match days {
+        Some(days) => days.resolve(&timings).into_iter().collect(),
+        None => all_days().collect(),
+    };
+    days.sort();
+
+    let statuses: Vec<DayStatus> = days
+        .into_iter()
+        .map(|day| {
+            if run.is_some() {
+                eprint!("\rChecking day {day}...");
+            }
+            collect(day, &timings, &readme, run)
+        })
+        .collect();
+
+    if run.is_some() {
+        eprint!("\r{:20}\r", "");
+    }
+    print!("{}", render(&statuses));
+}
+
+/// Gathers the status of `day`. Example tests and the solution are only run if `run` is set,
+/// and stopped if they take longer than it in total, not counting compilation.
+pub fn collect(day: Day, timings: &Timings, readme: &str, run: Option<Duration>) -> DayStatus {
+    let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+    let has_input = fs::metadata(format!("data/inputs/{day}.txt")).is_ok_and(|m| m.len() > 0);
+    let timing = timings.data.iter().find(|t| t.day == day).cloned();
+
+    let deadline = run.filter(|_| scaffolded).map(|timeout| {
+        build(day);
+        Instant::now() + timeout
+    });
+
+    DayStatus {
+        day,
+        scaffolded,
+        has_input,
+        has_example: Path::new(&format!("data/examples/{day}.txt")).exists(),
+        tests_pass: deadline.map(|deadline| run_tests(day, deadline)),
+        parts: match deadline {
+            Some(deadline) if has_input => run_parts(day, deadline),
+            _ => [None, None],
+        },
+        timing_stale: timings.is_day_stale(day),
+        readme_stale: readme_benchmarks::is_row_stale(readme, day, timing.as_ref()),
+        timing,
+    }
+}
+
+/// Compiles the tests and the solution of `day`, so that the timeout only covers running them.
+fn build(day: Day) {
+    for args in [
+        ["test", "--no-run", "--bin"],
+        ["build", "--release", "--bin"],
+    ] {
+        let _ = Command::new("cargo")
+            .args(args)
+            .args(["--quiet", &day.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+fn run_tests(day: Day, deadline: Instant) -> Outcome {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--quiet", "--bin", &day.to_string()]);
+    match output_until(&mut cmd, deadline) {
+        Ok(Some(output)) if output.status.success() => Outcome::Passed,
+        Ok(None) => Outcome::TimedOut,
+        _ => Outcome::Failed,
+    }
+}
+
+fn run_parts(day: Day, deadline: Instant) -> [Option<Outcome>; 2] {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--release", "--bin", &day.to_string()]);
+    let output = match output_until(&mut cmd, deadline) {
+        Ok(Some(output)) => output,
+        Ok(None) => return [Some(Outcome::TimedOut); 2],
+        Err(_) => return [None, None],
+    };
+
+    // a part printed `Part N: ✖` when it returned `None`, see `runner::print_result`.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let part = |n: u8| {
+        let prefix = format!("Part {n}: ");
+        stdout.lines().find(|l| l.starts_with(&prefix)).map(|l| {
+            if output.status.success() && !l.contains('✖') {
+                Outcome::Passed
+            } else {
+                Outcome::Failed
+            }
+        })
+    };
+    [part(1), part(2)]
+}
+
+/// Runs `cmd` and captures its standard output, or returns `None` if it's still running at
+/// `deadline`, after killing it.
+fn output_until(cmd: &mut Command, deadline: Instant) -> io::Result<Option<Output>> {
+    // a group of its own, to also kill the processes it starts, like the tests of `cargo test`.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(cmd, 0);
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::null()).spawn()?;
+
+    // read while it runs, a full pipe would block it.
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut buffer = vec![];
+        let _ = stdout.read_to_end(&mut buffer);
+        buffer
+    });
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(Output {
+                status,
+                stdout: reader.join().unwrap_or_default(),
+                stderr: vec![],
+            }));
+        }
+        if Instant::now() >= deadline {
+            kill(&mut child);
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(group) = i32::try_from(child.id()) {
+        // SAFETY: `kill` has no memory safety preconditions, the group was created for `child`.
+        unsafe { libc::kill(-group, libc::SIGKILL) };
+        return;
+    }
+    let _ = child.kill();
+}
+
+fn mark(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "✔",
+        Some(false) => "✖",
+        None => "-",
+    }
+}
+
+fn mark_outcome(outcome: Option<Outcome>) -> &'static str {
+    match outcome {
+        Some(Outcome::Passed) => mark(Some(true)),
+        Some(Outcome::Failed) => mark(Some(false)),
+        Some(Outcome::TimedOut) => "timeout",
+        None => mark(None),
+    }
+}
+
+/// Renders the statuses as a table, one row per day.
+pub fn render(statuses: &[DayStatus]) -> String {
+    let header = [
+        "Day", "Bin", "Input", "Example", "Tests", "Part 1", "Part 2", "Timing", "Bench", "README",
+    ];
+
+    let rows: Vec<[String; 10]> = statuses
+        .iter()
+        .map(|s| {
+            let timing = s.timing.as_ref().map_or_else(
+                || "-".to_string(),
+                |t| {
+                    format!(
+                        "{} / {}",
+                        t.part_1.map_or_else(|| "-".into(), |p| p.to_string()),
+                        t.part_2.map_or_else(|| "-".into(), |p| p.to_string())
+                    )
+                },
+            );
+            [
+                s.day.to_string(),
+                mark(Some(s.scaffolded)).into(),
+                mark(Some(s.has_input)).into(),
+                mark(Some(s.has_example)).into(),
+                mark_outcome(s.tests_pass).into(),
+                mark_outcome(s.parts[0]).into(),
+                mark_outcome(s.parts[1]).into(),
+                timing,
+                match (&s.timing, s.timing_stale) {
+                    (None, _) => "-",
+                    (Some(_), true) => "stale",
+                    (Some(_), false) => "ok",
+                }
+                .into(),
+                if s.readme_stale { "stale" } else { "ok" }.into(),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut s = String::new();
+    for (i, title) in header.iter().enumerate() {
+        let _ = write!(s, "{ANSI_BOLD}{title:<0$}{ANSI_RESET}  ", widths[i]);
+    }
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{cell:<0$}", widths[i]))
+            .collect();
+        let _ = write!(s, "\n{}", line.join("  ").trim_end());
+    }
+    let _ = writeln!(s);
+
+    let solved = statuses
+        .iter()
+        .filter(|s| s.timing.as_ref().is_some_and(Timing::is_complete))
+        .count();
+    let _ = writeln!(
+        s,
+        "\n{ANSI_ITALIC}{solved} of {} days fully benchmarked.{ANSI_RESET}",
+        statuses.len()
+    );
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, DayStatus, Outcome};
+    use crate::{day, template::timings::Timing};
+
+    #[test]
+    fn renders_statuses() {
+        let statuses = [
+            DayStatus {
+                day: day!(1),
+                scaffolded: true,
+                has_input: true,
+                has_example: true,
+                tests_pass: Some(Outcome::Passed),
+                parts: [Some(Outcome::Passed), Some(Outcome::Failed)],
+                timing: Some(Timing::new(day!(1), "10ms".parse().ok(), None)),
+                timing_stale: false,
+                readme_stale: true,
+            },
+            DayStatus {
+                day: day!(2),
+                scaffolded: false,
+                has_input: false,
+                has_example: false,
+                tests_pass: None,
+                parts: [None, None],
+                timing: None,
+                timing_stale: false,
+                readme_stale: false,
+            },
+            DayStatus {
+                day: day!(3),
+                scaffolded: true,
+                has_input: true,
+                has_example: true,
+                tests_pass: Some(Outcome::Passed),
+                parts: [Some(Outcome::TimedOut); 2],
+                timing: None,
+                timing_stale: false,
+                readme_stale: false,
+            },
+        ];
+
+        let table = render(&statuses);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(
+            lines[1].starts_with("01   ✔    ✔      ✔        ✔      ✔        ✖        10.0ms / -")
+        );
+        assert!(lines[1].ends_with("ok     stale"));
+        assert!(lines[2].starts_with("02   ✖    ✖      ✖        -      -        -        -"));
+        assert!(lines[3].contains("✔      timeout  timeout"));
+        assert!(table.contains("0 of 3 days fully benchmarked."));
+    }
+}
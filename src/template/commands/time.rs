@@ -1,30 +1,52 @@
-use std::collections::HashSet;
+use std::path::Path;
+use std::{process, thread};
 
+use crate::template::bench_conditions::BenchConditions;
+use crate::template::build_profile::BuildProfile;
+use crate::template::history::{self, Environment};
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{MergePolicy, Timings};
+use crate::template::timings_report::{self, ExportFormat};
+use crate::template::{all_days, readme_benchmarks, Day, DaySet, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    days: Option<&DaySet>,
+    run_all: bool,
+    store: bool,
+    policy: MergePolicy,
+    pin_cpu: Option<usize>,
+) {
+    // abort before benchmarking: storing onto timings that failed to parse would drop them.
+    let stored_timings = Timings::read_from_file_or_exit();
+    let conditions = check_conditions(pin_cpu);
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched and up to date.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings.is_day_complete(*day) || stored_timings.is_day_stale(*day)
+                    })
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.resolve(&stored_timings),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, &BuildProfile::release(), true, pin_cpu).unwrap();
+    for timing in &mut timings.data {
+        timing.conditions = Some(conditions.clone());
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        if let Err(e) = history::append(&timings, Environment::detect("release")) {
+            eprintln!("Failed to append to the timing history: {e}");
+        }
+
+        let merged_timings = stored_timings.merge(&timings, policy);
         merged_timings.store_file().unwrap();
 
         println!();
@@ -38,3 +60,65 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Benchmarks `days`, or every day, once per profile and prints how they compare. Nothing is stored.
+pub fn compare_profiles(days: Option<&DaySet>, profiles: &[BuildProfile], pin_cpu: Option<usize>) {
+    let days_to_run = days.map_or_else(
+        || all_days().collect(),
+        |days| days.resolve(&Timings::read_from_file_or_exit()),
+    );
+    check_conditions(pin_cpu);
+
+    let runs: Vec<(BuildProfile, Timings)> = profiles
+        .iter()
+        .map(|profile| {
+            println!("{ANSI_BOLD}Profile {profile}{ANSI_RESET}\n");
+            let timings = run_multi(&days_to_run, profile, true, pin_cpu).unwrap();
+            println!();
+            (profile.clone(), timings)
+        })
+        .collect();
+
+    print!("\n{}", timings_report::compare_profiles(&runs));
+}
+
+/// Validates `pin_cpu` and warns about conditions that make timings unstable.
+fn check_conditions(pin_cpu: Option<usize>) -> BenchConditions {
+    let cores = thread::available_parallelism().map_or(1, usize::from);
+    if let Some(cpu) = pin_cpu.filter(|cpu| *cpu >= cores) {
+        eprintln!("Error: cannot pin to CPU {cpu}, this machine has {cores}.");
+        process::exit(1);
+    }
+
+    // check before running, the solutions themselves raise the load.
+    let conditions = BenchConditions::detect(pin_cpu);
+    for warning in conditions.warnings() {
+        eprintln!("{ANSI_BOLD}Warning:{ANSI_RESET} {warning}");
+    }
+    conditions
+}
+
+/// Prints how the timings of `day` evolved over the stored runs.
+pub fn show_history(day: Day) {
+    print!("{}", history::render_trend(&history::read(), day));
+}
+
+/// Prints the stored timings in `format`.
+pub fn export(format: ExportFormat) {
+    print!(
+        "{}",
+        timings_report::export(&Timings::read_from_file_or_exit(), format)
+    );
+}
+
+/// Prints the stored timings next to the ones stored in `path`.
+pub fn compare_with(path: &Path) {
+    let theirs = Timings::read_from_path(path).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+    print!(
+        "{}",
+        timings_report::compare(&Timings::read_from_file_or_exit(), &theirs)
+    );
+}
@@ -0,0 +1,88 @@
+use std::io::{stdout, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::template::commands::countdown::format_remaining;
+use crate::template::commands::{download, read, scaffold};
+use crate::template::{aoc_cli, Day, SEASON_LENGTH};
+
+/// Number of download attempts when the input is not available yet.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock()
+    } else if let Some(day) = Day::today() {
+        day
+    } else {
+        eprintln!(
+            "`today` command can only be run between the 1st and \
+            the {}th of december. Please use `scaffold` with a specific day, \
+            or `today --wait` to wait for the next puzzle.",
+            SEASON_LENGTH
+        );
+        process::exit(1)
+    };
+
+    scaffold::handle(day, false);
+    if wait {
+        download_with_retry(day);
+    } else {
+        download::handle(day);
+    }
+    read::handle(day);
+}
+
+/// Sleeps until the next puzzle unlocks, showing a countdown.
+/// Returns right away if today's puzzle is already out.
+fn wait_for_unlock() -> Day {
+    if let Some(day) = Day::today() {
+        return day;
+    }
+
+    let (day, unlock) = Day::next_unlock(Utc::now());
+    println!(
+        "🎄 Waiting for day {} to unlock at {unlock}.",
+        day.into_inner()
+    );
+
+    loop {
+        let remaining = unlock.signed_duration_since(Utc::now());
+        if remaining.num_milliseconds() <= 0 {
+            break;
+        }
+        print!("\r⏳ {:<20}", format_remaining(remaining));
+        let _ = stdout().flush();
+        let step = remaining.num_milliseconds().clamp(0, 1000);
+        thread::sleep(Duration::from_millis(step.unsigned_abs()));
+    }
+    println!("\r🎄 Day {} is unlocked!      ", day.into_inner());
+    day
+}
+
+/// Downloads the puzzle, retrying with exponential backoff while the server doesn't serve it yet.
+fn download_with_retry(day: Day) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let mut delay = Duration::from_secs(2);
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match aoc_cli::download(day) {
+            Ok(_) => return,
+            Err(aoc_cli::AocCommandError::BadExitStatus(_)) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!("Download failed, retrying in {}s...", delay.as_secs());
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -3,12 +3,43 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Highest day number of any event.
+pub const MAX_DAYS: u8 = 25;
+
+/// Number of days in the configured event, the upper bound of [`Day::new`] and [`day!`](crate::day).
+///
+/// Read at compile time from the `AOC_DAYS` environment variable if set, otherwise derived from
+/// `AOC_YEAR`: events ran for 25 days until 2024 and for 12 days since 2025. Both are set in
+/// `.cargo/config.toml`.
+pub const SEASON_LENGTH: u8 = season_length_from(
+    option_env!("AOC_DAYS"),
+    match option_env!("AOC_YEAR") {
+        Some(year) => match u16::from_str_radix(year, 10) {
+            Ok(year) => Some(year),
+            Err(_) => None,
+        },
+        None => None,
+    },
+);
+
+const fn season_length_from(days: Option<&str>, year: Option<u16>) -> u8 {
+    if let Some(days) = days {
+        if let Ok(days @ 1..=MAX_DAYS) = u8::from_str_radix(days, 10) {
+            return days;
+        }
+    }
+    match year {
+        Some(year) if year >= 2025 => 12,
+        _ => MAX_DAYS,
+    }
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`SEASON_LENGTH`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +56,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > SEASON_LENGTH {
             return None;
         }
         Some(Self(day))
@@ -41,20 +72,59 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Whether this is the last day of the event, which only has one part.
+    pub fn is_last(self) -> bool {
+        self.0 == SEASON_LENGTH
+    }
+
+    /// Number of parts of the puzzle: 1 on the last day, 2 otherwise.
+    pub fn part_count(self) -> u8 {
+        if self.is_last() {
+            1
+        } else {
+            2
+        }
+    }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of the event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let today = Utc::now().with_timezone(&server_offset());
+        if today.month() == 12 {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the moment this day's puzzle unlocks in `year`: midnight, server time (UTC-5).
+    ///
+    /// # Panics
+    /// Panics if `year` is outside of the range supported by `chrono`.
+    pub fn unlock_time(self, year: i32) -> DateTime<FixedOffset> {
+        server_offset()
+            .with_ymd_and_hms(year, 12, u32::from(self.0), 0, 0, 0)
+            .single()
+            .expect("unlock time should be a valid date")
+    }
+
+    /// Returns the next puzzle to unlock after `now`, with its unlock time.
+    /// Once the last day of a year has unlocked, this is the first day of the next year.
+    pub fn next_unlock(now: DateTime<Utc>) -> (Self, DateTime<FixedOffset>) {
+        let year = now.with_timezone(&server_offset()).year();
+        all_days()
+            .map(|day| (day, day.unlock_time(year)))
+            .find(|(_, time)| *time > now)
+            .unwrap_or_else(|| (Self(1), Self(1).unlock_time(year + 1)))
+    }
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
 }
 
 impl Display for Day {
@@ -94,18 +164,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {SEASON_LENGTH}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event, see [`SEASON_LENGTH`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event, see [`SEASON_LENGTH`].
 pub struct AllDays {
     current: u8,
 }
@@ -121,10 +191,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > SEASON_LENGTH {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the season length.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +209,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::SEASON_LENGTH,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a day of the event, see `AOC_DAYS` and `AOC_YEAR`"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,38 +224,44 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, season_length_from, Day, SEASON_LENGTH};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
-
-        assert_eq!(iter.next(), Some(Day(1)));
-        assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
-        assert_eq!(iter.next(), None);
+        let days: Vec<u8> = all_days().map(Day::into_inner).collect();
+        assert_eq!(days, (1..=SEASON_LENGTH).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn configures_season_length() {
+        assert_eq!(season_length_from(None, None), 25);
+        assert_eq!(season_length_from(None, Some(2024)), 25);
+        assert_eq!(season_length_from(None, Some(2025)), 12);
+        assert_eq!(season_length_from(Some("12"), Some(2024)), 12);
+        assert_eq!(season_length_from(Some("30"), Some(2024)), 25);
+    }
+
+    #[test]
+    fn last_day_has_one_part() {
+        assert_eq!(Day(SEASON_LENGTH - 1).part_count(), 2);
+        assert_eq!(Day(SEASON_LENGTH).part_count(), 1);
+        assert_eq!(Day::new(SEASON_LENGTH + 1), None);
+    }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn computes_unlock_times() {
+        use chrono::{Datelike, TimeZone, Utc};
+
+        let unlock = Day(6).unlock_time(2024);
+        assert_eq!(unlock.to_rfc3339(), "2024-12-06T00:00:00-05:00");
+
+        let at = |y, m, d, h| Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+        assert_eq!(Day::next_unlock(at(2024, 6, 1, 0)).0, Day(1));
+        assert_eq!(Day::next_unlock(at(2024, 12, 6, 4)).0, Day(6));
+        assert_eq!(Day::next_unlock(at(2024, 12, 6, 5)).0, Day(7));
+        let (day, time) = Day::next_unlock(at(2024, 12, 26, 0));
+        assert_eq!((day, time.year()), (Day(1), 2025));
     }
 }
 
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day};
+
+/// A selection of days, as passed on the command line.
+///
+/// Parsed from a comma-separated list of terms:
+///  - `7`: a single day.
+///  - `1-5`, `12-`, `-3`: an inclusive range of days, open ends extend to the first or last day.
+///  - `all`: every day.
+///  - `unsolved`: days without a stored timing for every part.
+///  - `slowest:N`: the `N` days with the highest stored total time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Range(Day, Day),
+    Unsolved,
+    Slowest(usize),
+}
+
+impl DaySet {
+    /// Selects every day.
+    pub fn all() -> Self {
+        Self {
+            terms: vec![Term::Range(first_day(), last_day())],
+        }
+    }
+
+    /// Resolves the selection, reading stored timings if a term depends on them.
+    pub fn days(&self) -> HashSet<Day> {
+        let needs_timings = self
+            .terms
+            .iter()
+            .any(|t| matches!(t, Term::Unsolved | Term::Slowest(_)));
+
+        if needs_timings {
+            self.resolve(&Timings::read_from_file_or_exit())
+        } else {
+            self.resolve(&Timings::default())
+        }
+    }
+
+    pub(crate) fn resolve(&self, timings: &Timings) -> HashSet<Day> {
+        self.terms
+            .iter()
+            .flat_map(|term| -> Vec<Day> {
+                match term {
+                    Term::Range(start, end) => {
+                        all_days().filter(|d| d >= start && d <= end).collect()
+                    }
+                    Term::Unsolved => all_days()
+                        .filter(|d| !timings.is_day_complete(*d))
+                        .collect(),
+                    Term::Slowest(n) => {
+                        let mut data = timings.data.clone();
+                        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+                        data.into_iter().take(*n).map(|t| t.day).collect()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn first_day() -> Day {
+    all_days().next().unwrap()
+}
+
+fn last_day() -> Day {
+    all_days().last().unwrap()
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self {
+            terms: vec![Term::Range(day, day)],
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = s
+            .split(',')
+            .map(|term| parse_term(term.trim()).ok_or_else(|| DaySetFromStrError(term.into())))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { terms })
+    }
+}
+
+fn parse_term(term: &str) -> Option<Term> {
+    match term {
+        "all" => return Some(Term::Range(first_day(), last_day())),
+        "unsolved" => return Some(Term::Unsolved),
+        _ => {}
+    }
+
+    if let Some(n) = term.strip_prefix("slowest:") {
+        return n.parse().ok().map(Term::Slowest);
+    }
+
+    match term.split_once('-') {
+        None => term.parse().ok().map(|day| Term::Range(day, day)),
+        Some((start, end)) => {
+            let start = match start {
+                "" => first_day(),
+                s => s.parse().ok()?,
+            };
+            let end = match end {
+                "" => last_day(),
+                s => s.parse().ok()?,
+            };
+            (start <= end).then_some(Term::Range(start, end))
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting a day, a range like `1-5`, `all`, `unsolved` or `slowest:N`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::DaySet;
+    use crate::{
+        day,
+        template::timings::{mock_timings, Timings},
+        template::{Day, SEASON_LENGTH},
+    };
+
+    fn parse(s: &str) -> HashSet<Day> {
+        s.parse::<DaySet>().unwrap().resolve(&get_mock_timings())
+    }
+
+    fn get_mock_timings() -> Timings {
+        mock_timings(&[
+            (day!(1), Some("10ms"), Some("20ms")),
+            (day!(2), Some("30ms"), Some("40ms")),
+            (day!(4), Some("40ms"), None),
+        ])
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(parse("7"), HashSet::from([day!(7)]));
+        assert_eq!(
+            parse("1-3,7"),
+            HashSet::from([day!(1), day!(2), day!(3), day!(7)])
+        );
+        let last_days = (SEASON_LENGTH - 2..=SEASON_LENGTH).map(|d| Day::new(d).unwrap());
+        assert_eq!(
+            parse(&format!("{}-", SEASON_LENGTH - 2)),
+            HashSet::from_iter(last_days)
+        );
+        assert_eq!(parse("-2"), HashSet::from([day!(1), day!(2)]));
+        assert_eq!(parse("all").len(), SEASON_LENGTH.into());
+
+        // without keywords, no stored timings are needed.
+        let days: DaySet = format!("1-2,{}-", SEASON_LENGTH - 1).parse().unwrap();
+        assert_eq!(days.days().len(), 4);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(parse("slowest:2"), HashSet::from([day!(2), day!(4)]));
+        assert_eq!(parse("unsolved").len(), usize::from(SEASON_LENGTH) - 2);
+        assert!(!parse("unsolved").contains(&day!(1)));
+        assert!(parse("unsolved").contains(&day!(4)));
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        let after_last = SEASON_LENGTH + 1;
+        for s in ["", "0", "5-3", "slowest:", "foo", "1,,2"] {
+            assert!(s.parse::<DaySet>().is_err(), "{s} should not parse");
+        }
+        for s in [format!("{after_last}"), format!("1-{after_last}")] {
+            assert!(s.parse::<DaySet>().is_err(), "{s} should not parse");
+        }
+    }
+}
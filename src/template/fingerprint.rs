@@ -0,0 +1,199 @@
+/// Fingerprints of what a day's benchmark depends on: its solution, the library modules it uses
+/// and its input. A stored timing whose fingerprint differs from the current one is stale.
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::template::Day;
+
+const LIB_ROOT: &str = "src";
+
+/// Library modules that never count as a dependency of a solution.
+const IGNORED_MODULES: [&str; 1] = ["template"];
+
+/// Returns the fingerprint of `day`, or `None` if it has not been scaffolded.
+pub fn of(day: Day) -> Option<String> {
+    let bin = PathBuf::from(format!("{LIB_ROOT}/bin/{day}.rs"));
+    let source = fs::read_to_string(&bin).ok()?;
+
+    let mut hasher = Fnv1a::default();
+    hash_file(&mut hasher, &bin, source.as_bytes());
+
+    for path in dependencies(&source) {
+        if let Ok(content) = fs::read(&path) {
+            hash_file(&mut hasher, &path, &content);
+        }
+    }
+
+    let input = PathBuf::from(format!("data/inputs/{day}.txt"));
+    hash_file(&mut hasher, &input, &fs::read(&input).unwrap_or_default());
+
+    Some(format!("{:016x}", hasher.0))
+}
+
+fn hash_file(hasher: &mut Fnv1a, path: &Path, content: &[u8]) {
+    hasher.write(path.to_string_lossy().as_bytes());
+    hasher.write(&[0]);
+    hasher.write(&(content.len() as u64).to_le_bytes());
+    hasher.write(content);
+}
+
+/// Source files of the library modules `source` uses, including the modules those use in turn.
+fn dependencies(source: &str) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    let mut seen = BTreeSet::new();
+    let mut pending: Vec<String> = used_modules(source, "advent_of_code").into_iter().collect();
+
+    while let Some(module) = pending.pop() {
+        if IGNORED_MODULES.contains(&module.as_str()) || !seen.insert(module.clone()) {
+            continue;
+        }
+        for file in module_files(&module) {
+            if let Ok(content) = fs::read_to_string(&file) {
+                pending.extend(used_modules(&content, "crate"));
+            }
+            files.insert(file);
+        }
+    }
+
+    files
+}
+
+/// `src/{module}.rs`, or every file below `src/{module}/`.
+fn module_files(module: &str) -> Vec<PathBuf> {
+    let file = PathBuf::from(format!("{LIB_ROOT}/{module}.rs"));
+    if file.exists() {
+        return vec![file];
+    }
+
+    let mut files = vec![];
+    let mut dirs = vec![PathBuf::from(format!("{LIB_ROOT}/{module}"))];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Names of the top-level modules referenced as `{root}::module` or `{root}::{module, ...}`.
+fn used_modules(source: &str, root: &str) -> BTreeSet<String> {
+    let pattern = format!("{root}::");
+    let mut modules = BTreeSet::new();
+
+    for (i, _) in source.match_indices(&pattern) {
+        let preceded_by_ident = source[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if preceded_by_ident {
+            continue;
+        }
+
+        let rest = &source[i + pattern.len()..];
+        match rest.strip_prefix('{') {
+            Some(group) => modules.extend(group_items(group)),
+            None => modules.extend(leading_ident(rest)),
+        }
+    }
+
+    modules
+}
+
+/// First identifier of every top-level item of a `{...}` use group, `group` starting after `{`.
+fn group_items(group: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut item_start = true;
+
+    for (i, c) in group.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => break,
+            '}' => depth -= 1,
+            ',' if depth == 0 => item_start = true,
+            c if c.is_whitespace() => {}
+            _ if item_start && depth == 0 => {
+                items.extend(leading_ident(&group[i..]));
+                item_start = false;
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+fn leading_ident(s: &str) -> Option<String> {
+    let end = s
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(s.len());
+    // `self` and friends don't name a module, and a lowercase ident followed by `!` is a macro.
+    let ident = &s[..end];
+    let is_module = !ident.is_empty()
+        && !["self", "super", "crate"].contains(&ident)
+        && !s[end..].starts_with('!')
+        && ident.starts_with(|c: char| c.is_lowercase());
+    is_module.then(|| ident.to_string())
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions unlike `DefaultHasher`.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{used_modules, Fnv1a};
+
+    #[test]
+    fn finds_used_modules() {
+        let source = "
+            advent_of_code::solution!(6);
+            use advent_of_code::collections::{BitGrid, FastHashMap};
+            use advent_of_code::{cycle, math::number_theory::{gcd, lcm}, memo::Memo};
+            use std::collections::HashMap;
+            fn f() { my_advent_of_code::foo(); advent_of_code::template::read_file(); }
+        ";
+        let modules: Vec<String> = used_modules(source, "advent_of_code").into_iter().collect();
+        assert_eq!(
+            modules,
+            ["collections", "cycle", "math", "memo", "template"]
+        );
+    }
+
+    #[test]
+    fn finds_crate_references() {
+        let source = "use crate::collections::FastHashMap;\nuse super::rational::Rational;";
+        let modules: Vec<String> = used_modules(source, "crate").into_iter().collect();
+        assert_eq!(modules, ["collections"]);
+    }
+
+    #[test]
+    fn hashes_with_fnv1a() {
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.0, 0xaf63_dc4c_8601_ec8c);
+    }
+}
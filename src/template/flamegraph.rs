@@ -0,0 +1,215 @@
+/// Renders a [`Profile`] as an SVG flame graph: every function is a box on top of its caller,
+/// as wide as the share of samples it was on the stack for.
+use std::fmt::Write as _;
+
+use crate::template::profiler::Profile;
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const PADDING: f64 = 10.0;
+const TITLE_HEIGHT: f64 = 30.0;
+const FONT_SIZE: f64 = 11.0;
+/// Approximate width of a character at [`FONT_SIZE`].
+const CHAR_WIDTH: f64 = 6.5;
+/// Boxes narrower than this are left out.
+const MIN_WIDTH: f64 = 0.1;
+
+/// A function and the functions it called, merged over all samples.
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    samples: usize,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn insert(&mut self, stack: &[String], samples: usize) {
+        self.samples += samples;
+        let Some((name, rest)) = stack.split_first() else {
+            return;
+        };
+        let child = match self.children.iter().position(|c| &c.name == name) {
+            Some(i) => &mut self.children[i],
+            None => {
+                self.children.push(Node {
+                    name: name.clone(),
+                    ..Node::default()
+                });
+                self.children.last_mut().unwrap()
+            }
+        };
+        child.insert(rest, samples);
+    }
+
+    fn depth(&self) -> usize {
+        1 + self.children.iter().map(Node::depth).max().unwrap_or(0)
+    }
+
+    /// Sorts children by name, so that the graph doesn't depend on the sampling order.
+    fn sort(&mut self) {
+        self.children.sort_by(|a, b| a.name.cmp(&b.name));
+        self.children.iter_mut().for_each(Node::sort);
+    }
+}
+
+/// Renders `profile` as an SVG document titled `title`.
+pub fn render(profile: &Profile, title: &str) -> String {
+    let mut root = Node {
+        name: "all".into(),
+        ..Node::default()
+    };
+    for (stack, samples) in &profile.stacks {
+        root.insert(stack, *samples);
+    }
+    root.sort();
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = TITLE_HEIGHT + root.depth() as f64 * FRAME_HEIGHT + 2.0 * PADDING;
+
+    let mut s = String::new();
+    let _ = writeln!(
+        s,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="{FONT_SIZE}">"#
+    );
+    let _ = writeln!(
+        s,
+        r##"<rect width="{WIDTH}" height="{height}" fill="#fdf6e3"/>"##
+    );
+    let _ = writeln!(
+        s,
+        r#"<text x="{}" y="20" text-anchor="middle" font-size="15">{} ({} samples)</text>"#,
+        WIDTH / 2.0,
+        escape(title),
+        root.samples
+    );
+
+    if root.samples > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let scale = (WIDTH - 2.0 * PADDING) / root.samples as f64;
+        render_node(
+            &mut s,
+            &root,
+            root.samples,
+            PADDING,
+            height - PADDING,
+            scale,
+        );
+    }
+
+    s.push_str("</svg>\n");
+    s
+}
+
+/// Draws `node` with its bottom left corner at `x`, `y` and its children on top.
+fn render_node(s: &mut String, node: &Node, total: usize, x: f64, y: f64, scale: f64) {
+    #[allow(clippy::cast_precision_loss)]
+    let width = node.samples as f64 * scale;
+    if width < MIN_WIDTH {
+        return;
+    }
+
+    let top = y - FRAME_HEIGHT;
+    let name = escape(&node.name);
+    #[allow(clippy::cast_precision_loss)]
+    let percent = node.samples as f64 / total as f64 * 100.0;
+    let _ = write!(
+        s,
+        r#"<g><title>{name} ({} samples, {percent:.2}%)</title><rect x="{x:.2}" y="{top}" width="{width:.2}" height="{}" fill="{}" rx="2"/>"#,
+        node.samples,
+        FRAME_HEIGHT - 1.0,
+        color(&node.name),
+    );
+    if let Some(label) = label(&node.name, width) {
+        let _ = write!(
+            s,
+            r#"<text x="{:.2}" y="{}">{}</text>"#,
+            x + 3.0,
+            top + FRAME_HEIGHT - 4.5,
+            escape(&label)
+        );
+    }
+    s.push_str("</g>\n");
+
+    let mut child_x = x;
+    for child in &node.children {
+        render_node(s, child, total, child_x, top, scale);
+        #[allow(clippy::cast_precision_loss)]
+        let child_width = child.samples as f64 * scale;
+        child_x += child_width;
+    }
+}
+
+/// The part of `name` that fits into a box of `width`, `None` if not even a few characters fit.
+fn label(name: &str, width: f64) -> Option<String> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fits = ((width - 6.0) / CHAR_WIDTH).max(0.0) as usize;
+    let len = name.chars().count();
+    if len <= fits {
+        Some(name.to_string())
+    } else if fits >= 4 {
+        Some(name.chars().take(fits - 2).chain("..".chars()).collect())
+    } else {
+        None
+    }
+}
+
+/// A warm color derived from `name`, stable across runs.
+fn color(name: &str) -> String {
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |h, b| {
+        (h ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    });
+    let (r, g, b) = (205 + hash % 50, 90 + (hash >> 8) % 140, (hash >> 16) % 55);
+    format!("rgb({r},{g},{b})")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{label, render};
+    use crate::template::profiler::Profile;
+
+    fn stack(frames: &[&str]) -> Vec<String> {
+        frames.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn renders_nested_frames() {
+        let mut profile = Profile::default();
+        profile.stacks.insert(stack(&["main", "fill"]), 3);
+        profile.stacks.insert(stack(&["main", "parse"]), 1);
+        profile.stacks.insert(stack(&["<Region as Fill>::fill"]), 4);
+
+        let svg = render(&profile, "Day 12 part 1");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Day 12 part 1 (8 samples)"));
+        assert!(svg.contains("<title>all (8 samples, 100.00%)</title>"));
+        assert!(svg.contains("<title>main (4 samples, 50.00%)</title>"));
+        assert!(svg.contains("<title>fill (3 samples, 37.50%)</title>"));
+        assert!(svg.contains("&lt;Region as Fill&gt;::fill"));
+    }
+
+    #[test]
+    fn renders_empty_profiles() {
+        let svg = render(&Profile::default(), "empty");
+        assert!(svg.contains("(0 samples)"));
+        assert!(!svg.contains("<rect x="));
+    }
+
+    #[test]
+    fn truncates_labels() {
+        assert_eq!(label("main", 100.0).as_deref(), Some("main"));
+        assert_eq!(
+            label("a_long_function_name", 50.0).as_deref(),
+            Some("a_lo..")
+        );
+        assert_eq!(label("main", 10.0), None);
+    }
+}
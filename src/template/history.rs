@@ -0,0 +1,342 @@
+/// History of stored benchmark runs, one JSON object per line, with the environment they ran in.
+/// Lets `cargo time --history` show how a day's runtime evolved and what changed in between.
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Where a benchmark run happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub cpu: Option<String>,
+    pub profile: String,
+}
+
+/// A single `cargo time --store` run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub environment: Environment,
+    pub timings: Timings,
+}
+
+impl Environment {
+    /// Detects the current environment. Anything that can't be detected is left out.
+    pub fn detect(profile: &str) -> Self {
+        Self {
+            commit: git_commit(),
+            rustc: command_output("rustc", &["--version"]),
+            cpu: cpu_model(),
+            profile: profile.into(),
+        }
+    }
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !stdout.trim().is_empty()).then(|| stdout.trim().to_string())
+}
+
+fn git_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain"]).is_some();
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return cpuinfo
+            .lines()
+            .find(|l| l.starts_with("model name"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+    }
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+/// Appends a run of `timings` to the history file.
+pub fn append(timings: &Timings, environment: Environment) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    // recent runs only matter for merging, the history keeps them on its own.
+    let data = timings
+        .data
+        .iter()
+        .map(|t| Timing {
+            recent: Default::default(),
+            ..t.clone()
+        })
+        .collect();
+
+    let run = Run {
+        timestamp,
+        environment,
+        timings: Timings { data },
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+    let line = JsonValue::from(&run)
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    writeln!(file, "{line}")
+}
+
+/// Reads every recorded run, skipping lines that can't be parsed.
+pub fn read() -> Vec<Run> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| match Run::try_from(l) {
+            Ok(run) => Some(run),
+            Err(e) => {
+                eprintln!("Skipping invalid history entry: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Renders the runs that measured `day`, with the change of each part relative to the previous run.
+pub fn render_trend(runs: &[Run], day: Day) -> String {
+    let rows: Vec<(&Run, &Timing)> = runs
+        .iter()
+        .filter_map(|run| Some((run, run.timings.data.iter().find(|t| t.day == day)?)))
+        .collect();
+
+    let mut s = format!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n");
+    if rows.is_empty() {
+        s.push_str("No recorded runs.\n");
+        return s;
+    }
+
+    let mut lines = vec![[
+        "Date".to_string(),
+        "Commit".into(),
+        "rustc".into(),
+        "Profile".into(),
+        "Part 1".into(),
+        "Part 2".into(),
+        "CPU".into(),
+    ]];
+    let mut previous: [Option<PartTiming>; 2] = [None, None];
+
+    for (run, timing) in rows {
+        let env = &run.environment;
+        let mut parts = [String::new(), String::new()];
+        for (i, part) in [&timing.part_1, &timing.part_2].into_iter().enumerate() {
+            parts[i] = match part {
+                None => "-".into(),
+                Some(part) => match previous[i].and_then(|p| change(p, *part)) {
+                    Some(change) => format!("{part} ({change:+.1}%)"),
+                    None => part.to_string(),
+                },
+            };
+            if part.is_some() {
+                previous[i] = *part;
+            }
+        }
+        let [part_1, part_2] = parts;
+
+        lines.push([
+            format_timestamp(run.timestamp),
+            env.commit.clone().unwrap_or_else(|| "-".into()),
+            env.rustc
+                .as_deref()
+                .and_then(|v| v.split_whitespace().nth(1))
+                .unwrap_or("-")
+                .into(),
+            env.profile.clone(),
+            part_1,
+            part_2,
+            env.cpu.clone().unwrap_or_else(|| "-".into()),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..7)
+        .map(|i| lines.iter().map(|l| l[i].chars().count()).max().unwrap())
+        .collect();
+    for line in lines {
+        let cells: Vec<String> = line
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{cell:<0$}", widths[i]))
+            .collect();
+        let _ = writeln!(s, "{}", cells.join("  ").trim_end());
+    }
+    s
+}
+
+/// Relative change from `before` to `after`, in percent.
+fn change(before: PartTiming, after: PartTiming) -> Option<f64> {
+    let (before, after) = (before.nanos, after.nanos);
+    (before > 0.0).then(|| (after - before) / before * 100.0)
+}
+
+/// Formats a unix timestamp as a UTC date, e.g. `2024-12-06 05:00`.
+fn format_timestamp(timestamp: u64) -> String {
+    // see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let secs = timestamp % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map = match JsonValue::from(value.timings.clone()) {
+            JsonValue::Object(map) => map,
+            _ => HashMap::new(),
+        };
+        let env = &value.environment;
+        let optional = |v: &Option<String>| v.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional(&env.commit));
+        map.insert("rustc".into(), optional(&env.rustc));
+        map.insert("cpu".into(), optional(&env.cpu));
+        map.insert("profile".into(), JsonValue::String(env.profile.clone()));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Run {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected run to be an object.")?;
+
+        let optional = |key: &str| map.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = map
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("expected run.timestamp to be a number.")?;
+
+        let data = map
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp,
+            environment: Environment {
+                commit: optional("commit"),
+                rustc: optional("rustc"),
+                cpu: optional("cpu"),
+                profile: optional("profile").unwrap_or_else(|| "release".into()),
+            },
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, render_trend, Environment, Run};
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+
+    fn run(timestamp: u64, rustc: &str, part_1: &str, part_2: Option<&str>) -> Run {
+        Run {
+            timestamp,
+            environment: Environment {
+                commit: Some("abc1234".into()),
+                rustc: Some(format!("rustc {rustc} (90b35a623 2024-11-26)")),
+                cpu: None,
+                profile: "release".into(),
+            },
+            timings: Timings {
+                data: vec![Timing::new(
+                    day!(6),
+                    part_1.parse().ok(),
+                    part_2.map(|p| p.parse().unwrap()),
+                )],
+            },
+        }
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_461_200), "2024-12-06 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn serializes_runs() {
+        let original = run(1_733_461_200, "1.83.0", "1ms", None);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        let parsed = Run::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.timestamp, original.timestamp);
+        assert_eq!(parsed.environment, original.environment);
+        assert_eq!(parsed.timings.data[0].part_1, "1ms".parse().ok());
+    }
+
+    #[test]
+    fn renders_trends() {
+        let runs = [
+            run(1_733_461_200, "1.83.0", "2ms", Some("10ms")),
+            run(1_733_547_600, "1.84.0", "1ms", None),
+        ];
+        let trend = render_trend(&runs, day!(6));
+        let lines: Vec<&str> = trend.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].starts_with("2024-12-06 05:00  abc1234  1.83.0  release  2.0ms"));
+        assert!(lines[3].contains("1.84.0  release  1.0ms (-50.0%)  -"));
+        assert!(render_trend(&runs, day!(7)).contains("No recorded runs."));
+    }
+}
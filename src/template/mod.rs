@@ -1,20 +1,41 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
 pub mod runner;
 
+pub use build_profile::BuildProfile;
 pub use day::*;
+pub use day_set::*;
+pub use timings::MergePolicy;
+pub use timings_report::ExportFormat;
 
+mod answers;
+mod bench_conditions;
+mod benchmark_chart;
+mod build_profile;
 mod day;
+mod day_set;
+mod fingerprint;
+mod flamegraph;
+mod history;
+mod profiler;
 mod readme_benchmarks;
+mod readme_progress;
 mod run_multi;
 mod timings;
+mod timings_report;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// The event year, from the `AOC_YEAR` environment variable.
+pub fn aoc_year() -> Option<u16> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
@@ -39,19 +60,39 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternative implementations of a part, e.g. a brute-force reference, can be listed after it as
+/// `part_one: [part_one_naive]` or `part_two: [...]`. They must agree with the part on the example,
+/// which a generated test checks, and `cargo solve --variants` runs and benchmarks them side by side.
+/// `cargo shrink` uses them as oracles to minimize inputs the part gets wrong.
+///
+/// The generated `main` accepts `--part <1|2>`, `--input <path>` (`-` for stdin) and `--example`
+/// to pick what to run at runtime.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1, []] [part_two, 2, []]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1, []]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2, []]);
+    };
+    ($day:expr, 1, part_one: [$($variant:ident),+ $(,)?]) => {
+        $crate::solution!(@impl $day, [part_one, 1, [$($variant),+]]);
+        $crate::solution!(@variant_tests [part_one, 1, [$($variant),+]]);
+    };
+    ($day:expr, 2, part_two: [$($variant:ident),+ $(,)?]) => {
+        $crate::solution!(@impl $day, [part_two, 2, [$($variant),+]]);
+        $crate::solution!(@variant_tests [part_two, 2, [$($variant),+]]);
+    };
+    ($day:expr $(, part_one: [$($one:ident),+ $(,)?])? $(, part_two: [$($two:ident),+ $(,)?])? $(,)?) => {
+        $crate::solution!(@impl $day, [part_one, 1, [$($($one),+)?]] [part_two, 2, [$($($two),+)?]]);
+        $crate::solution!(@variant_tests [part_one, 1, [$($($one),+)?]] [part_two, 2, [$($($two),+)?]]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:ident, $part:expr, [$($variant:ident),*]] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +102,51 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_env(&[$($part),*]);
+            let input = options.read_input(DAY);
+            $(
+                if options.runs_part($part) {
+                    let variants = [
+                        $crate::variant!($func),
+                        $( $crate::variant!($variant), )*
+                    ];
+                    if let Some(oracle) = &options.shrink {
+                        shrink_part(&variants, &input, DAY, $part, oracle);
+                    } else if options.variants {
+                        run_variants(&variants, &input, $part);
+                    } else {
+                        run_part($func, &input, DAY, $part, &options);
+                    }
+                }
+            )*
+        }
+    };
+
+    (@variant_tests $( [$func:ident, $part:expr, [$($variant:ident),*]] )*) => {
+        #[cfg(test)]
+        #[test]
+        fn variants_agree_on_example() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("examples", DAY);
+            $(
+                let variants = [
+                    $crate::variant!($func),
+                    $( $crate::variant!($variant), )*
+                ];
+                assert_variants_agree(&variants, &input);
+            )*
+        }
+    };
+}
+
+/// A [`Variant`](crate::template::runner::Variant) of a solution part named after its function,
+/// e.g. `variant!(part_one_naive)` to compare it with `variant!(part_one)` in a property test.
+#[macro_export]
+macro_rules! variant {
+    ($func:ident) => {
+        $crate::template::runner::Variant {
+            name: stringify!($func),
+            func: $func,
         }
     };
 }
@@ -0,0 +1,336 @@
+/// In-process sampling profiler behind `cargo solve --flamegraph`.
+/// A `SIGPROF` timer interrupts the process every millisecond of CPU time. The signal handler only
+/// walks the frame pointers of the interrupted code into a buffer allocated up front, which is
+/// async-signal-safe. Addresses are resolved to function names once profiling has stopped.
+/// Stacks are only complete in code built with `-C force-frame-pointers=yes`, which
+/// `cargo solve --flamegraph` does. Supported on Linux on x86_64 and aarch64.
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+
+/// Stacks sampled while profiling, outermost function first, with how often each was seen.
+#[derive(Debug, Default)]
+pub struct Profile {
+    pub stacks: HashMap<Vec<String>, usize>,
+}
+
+impl Profile {
+    pub fn samples(&self) -> usize {
+        self.stacks.values().sum()
+    }
+}
+
+/// Calls `f` over and over for at least `duration`, sampling where it spends its time.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub fn profile(f: impl FnMut(), duration: Duration) -> io::Result<Profile> {
+    linux::profile(f, duration)
+}
+
+/// Calls `f` over and over for at least `duration`, sampling where it spends its time.
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+pub fn profile(_f: impl FnMut(), _duration: Duration) -> io::Result<Profile> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "profiling is only supported on Linux on x86_64 and aarch64",
+    ))
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod linux {
+    use std::collections::HashMap;
+    use std::ffi::c_void;
+    use std::io;
+    use std::mem::{size_of, zeroed};
+    use std::ptr::{self, null_mut};
+    use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use super::Profile;
+
+    const INTERVAL_MICROS: libc::suseconds_t = 1000;
+
+    const MAX_SAMPLES: usize = 8192;
+    const MAX_DEPTH: usize = 128;
+
+    /// A sampled stack: the interrupted instruction, then return addresses, innermost first.
+    #[derive(Clone, Copy)]
+    struct Sample {
+        len: usize,
+        frames: [usize; MAX_DEPTH],
+    }
+
+    // the signal handler can't allocate, it writes to slots of a buffer allocated up front.
+    static BUFFER: AtomicPtr<Sample> = AtomicPtr::new(null_mut());
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    // the profiled thread and its stack, frame pointers outside of it are never followed.
+    static THREAD: AtomicUsize = AtomicUsize::new(0);
+    static STACK_LOW: AtomicUsize = AtomicUsize::new(0);
+    static STACK_HIGH: AtomicUsize = AtomicUsize::new(0);
+
+    /// Program counter and frame pointer of the interrupted code.
+    ///
+    /// # Safety
+    /// `context` must be the `ucontext_t` passed to a `SA_SIGINFO` handler.
+    #[cfg(target_arch = "x86_64")]
+    unsafe fn registers(context: *const libc::ucontext_t) -> (usize, usize) {
+        let gregs = &(*context).uc_mcontext.gregs;
+        #[allow(clippy::cast_sign_loss)]
+        (
+            gregs[libc::REG_RIP as usize] as usize,
+            gregs[libc::REG_RBP as usize] as usize,
+        )
+    }
+
+    /// Program counter and frame pointer of the interrupted code.
+    ///
+    /// # Safety
+    /// `context` must be the `ucontext_t` passed to a `SA_SIGINFO` handler.
+    #[cfg(target_arch = "aarch64")]
+    unsafe fn registers(context: *const libc::ucontext_t) -> (usize, usize) {
+        let mcontext = &(*context).uc_mcontext;
+        #[allow(clippy::cast_possible_truncation)]
+        (mcontext.pc as usize, mcontext.regs[29] as usize)
+    }
+
+    extern "C" fn on_sigprof(_: libc::c_int, _: *mut libc::siginfo_t, context: *mut c_void) {
+        let buffer = BUFFER.load(Ordering::Acquire);
+        // SAFETY: `pthread_self` only reads thread-local state.
+        #[allow(clippy::useless_conversion)]
+        let thread = usize::try_from(unsafe { libc::pthread_self() }).unwrap_or(0);
+        if buffer.is_null() || thread != THREAD.load(Ordering::Relaxed) {
+            return;
+        }
+        let i = NEXT.fetch_add(1, Ordering::Relaxed);
+        if i >= MAX_SAMPLES {
+            return;
+        }
+
+        // SAFETY: every slot is handed out once and the buffer outlives the timer.
+        let sample = unsafe { &mut *buffer.add(i) };
+        // SAFETY: the kernel passes the interrupted context to `SA_SIGINFO` handlers.
+        let (pc, mut fp) = unsafe { registers(context.cast()) };
+        sample.frames[0] = pc;
+        sample.len = 1;
+
+        // every frame starts with the caller's frame pointer, followed by the return address.
+        let (low, high) = (
+            STACK_LOW.load(Ordering::Relaxed),
+            STACK_HIGH.load(Ordering::Relaxed),
+        );
+        while sample.len < MAX_DEPTH
+            && fp >= low
+            && fp + 2 * size_of::<usize>() <= high
+            && fp % size_of::<usize>() == 0
+        {
+            let frame = fp as *const usize;
+            // SAFETY: `fp` was checked to point into the stack of this thread, which is mapped.
+            let (caller_fp, return_address) =
+                unsafe { (ptr::read_volatile(frame), ptr::read_volatile(frame.add(1))) };
+            if return_address == 0 {
+                break;
+            }
+            sample.frames[sample.len] = return_address;
+            sample.len += 1;
+            // stacks grow down, a caller's frame is always above.
+            if caller_fp <= fp {
+                break;
+            }
+            fp = caller_fp;
+        }
+    }
+
+    fn check(result: libc::c_int) -> io::Result<()> {
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Bounds of the stack of the calling thread.
+    fn stack_bounds() -> io::Result<(usize, usize)> {
+        // SAFETY: `attr` is initialized by `pthread_getattr_np` before it is read and destroyed once.
+        unsafe {
+            let mut attr: libc::pthread_attr_t = zeroed();
+            let result = libc::pthread_getattr_np(libc::pthread_self(), &mut attr);
+            if result != 0 {
+                return Err(io::Error::from_raw_os_error(result));
+            }
+            let (mut low, mut size) = (null_mut(), 0);
+            let result = libc::pthread_attr_getstack(&attr, &mut low, &mut size);
+            libc::pthread_attr_destroy(&mut attr);
+            if result != 0 {
+                return Err(io::Error::from_raw_os_error(result));
+            }
+            Ok((low as usize, low as usize + size))
+        }
+    }
+
+    fn set_timer(micros: libc::suseconds_t) -> io::Result<()> {
+        let interval = libc::timeval {
+            tv_sec: 0,
+            tv_usec: micros,
+        };
+        let timer = libc::itimerval {
+            it_interval: interval,
+            it_value: interval,
+        };
+        // SAFETY: `timer` is a valid `itimerval` for the duration of the call.
+        check(unsafe { libc::setitimer(libc::ITIMER_PROF, &timer, null_mut()) })
+    }
+
+    /// Where samples start: frames outside of it belong to the profiler and the runner.
+    #[inline(never)]
+    fn run_repeatedly(f: &mut dyn FnMut(), duration: Duration) {
+        let start = Instant::now();
+        loop {
+            f();
+            if start.elapsed() >= duration {
+                break;
+            }
+        }
+    }
+
+    pub fn profile(mut f: impl FnMut(), duration: Duration) -> io::Result<Profile> {
+        let (low, high) = stack_bounds()?;
+        STACK_LOW.store(low, Ordering::Relaxed);
+        STACK_HIGH.store(high, Ordering::Relaxed);
+        // SAFETY: `pthread_self` has no preconditions.
+        #[allow(clippy::useless_conversion)]
+        THREAD.store(
+            usize::try_from(unsafe { libc::pthread_self() }).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+
+        let mut buffer = vec![
+            Sample {
+                len: 0,
+                frames: [0; MAX_DEPTH],
+            };
+            MAX_SAMPLES
+        ];
+        NEXT.store(0, Ordering::Relaxed);
+        BUFFER.store(buffer.as_mut_ptr(), Ordering::Release);
+
+        // SAFETY: `action` is fully initialized, `on_sigprof` only touches the atomics, the
+        // buffer and the stack of the profiled thread.
+        let mut previous: libc::sigaction = unsafe { zeroed() };
+        let installed = unsafe {
+            let mut action: libc::sigaction = zeroed();
+            action.sa_sigaction = on_sigprof
+                as extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void)
+                as libc::sighandler_t;
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            check(libc::sigaction(libc::SIGPROF, &action, &mut previous))
+        };
+        let started = installed.and_then(|()| set_timer(INTERVAL_MICROS));
+        if started.is_ok() {
+            run_repeatedly(&mut f, duration);
+        }
+
+        let _ = set_timer(0);
+        // SAFETY: restores the handler that was installed before.
+        unsafe { libc::sigaction(libc::SIGPROF, &previous, null_mut()) };
+        BUFFER.store(null_mut(), Ordering::Release);
+        started?;
+
+        let count = NEXT.load(Ordering::Relaxed).min(MAX_SAMPLES);
+        let mut names = HashMap::new();
+        let mut profile = Profile::default();
+        for sample in &buffer[..count] {
+            let stack = resolve(&mut names, &sample.frames[..sample.len]);
+            *profile.stacks.entry(stack).or_default() += 1;
+        }
+        Ok(profile)
+    }
+
+    /// Turns the addresses of a sample into function names, outermost first, caching them in
+    /// `names`. Frames outside of `run_repeatedly` are left out. If it is missing, frames weren't
+    /// linked by frame pointers, and only the interrupted function is kept below `[truncated]`.
+    fn resolve(names: &mut HashMap<usize, Vec<String>>, frames: &[usize]) -> Vec<String> {
+        let mut stack: Vec<String> = vec![];
+        for (i, &address) in frames.iter().enumerate() {
+            // return addresses point after the call, look up the call instruction itself.
+            let address = if i == 0 { address } else { address - 1 };
+            let functions = names.entry(address).or_insert_with(|| symbolize(address));
+            if functions.iter().any(|f| f.ends_with("::run_repeatedly")) {
+                stack.reverse();
+                return stack;
+            }
+            stack.extend(functions.iter().cloned());
+        }
+
+        let leaf = frames.first().map_or(0, |&address| names[&address].len());
+        stack.truncate(leaf);
+        stack.push("[truncated]".into());
+        stack.reverse();
+        stack
+    }
+
+    /// Names of the functions at `address`, innermost inlined one first.
+    fn symbolize(address: usize) -> Vec<String> {
+        let mut functions = vec![];
+        backtrace::resolve(address as *mut c_void, |symbol| {
+            if let Some(name) = symbol.name() {
+                // the alternate format leaves out the hash of legacy symbols.
+                functions.push(format!("{name:#}"));
+            }
+        });
+        if functions.is_empty() {
+            functions.push("[unknown]".into());
+        }
+        functions
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod tests {
+    use std::hint::black_box;
+    use std::time::Duration;
+
+    use super::profile;
+
+    /// Calls no other function, so it is sampled even without frame pointers.
+    #[inline(never)]
+    fn spin() -> u64 {
+        let (mut acc, mut n) = (0, 0);
+        while n < 100_000 {
+            acc = (acc + (n & 0xff)) % 1_000_003;
+            n += 1;
+        }
+        acc
+    }
+
+    #[test]
+    fn samples_the_profiled_function() {
+        let profile = profile(
+            || {
+                black_box(spin());
+            },
+            Duration::from_millis(300),
+        )
+        .unwrap();
+
+        assert!(profile.samples() > 0);
+        assert!(profile
+            .stacks
+            .keys()
+            .any(|stack| stack.iter().any(|f| f.ends_with("::spin"))));
+    }
+}
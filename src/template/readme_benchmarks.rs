@@ -2,8 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::benchmark_chart::{self, CHART_PATH};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,8 +22,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +31,9 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the section of `readme` enclosed by two occurences of `marker`.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -59,19 +61,14 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
+        format!("![Benchmarks]({CHART_PATH})"),
+        String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    for timing in &timings.data {
+        lines.push(format_row(timing));
     }
 
     lines.push(String::new());
@@ -81,50 +78,73 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+pub fn format_row(timing: &Timing) -> String {
+    // the last day has no second part, leave its cell empty rather than marking it missing.
+    let part_2 = match &timing.part_2 {
+        None if timing.day.part_count() == 1 => String::new(),
+        Some(part_2) => format!("`{part_2}`"),
+        None => "`-`".into(),
+    };
+    format!(
+        "| [Day {}]({}) | `{}` | {part_2} |",
+        timing.day.into_inner(),
+        get_path_for_bin(timing.day),
+        timing.part_1.map_or_else(|| "-".into(), |p| p.to_string()),
+    )
+}
+
+/// Whether the README row of `day` differs from the row `timing` would produce.
+/// A row without a timing, or a timing without a row, is stale too.
+pub fn is_row_stale(readme: &str, day: Day, timing: Option<&Timing>) -> bool {
+    let table = match locate_table(readme, MARKER) {
+        Ok(pos) => &readme[pos.pos_start..pos.pos_end],
+        Err(_) => "",
+    };
+    let prefix = format!("| [Day {}](", day.into_inner());
+    let row = table.lines().find(|line| line.starts_with(&prefix));
+
+    match (row, timing) {
+        (Some(row), Some(timing)) => row != format_row(timing),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+/// Rewrites the benchmark section of the README and the chart it references.
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings.clone(), total_millis)?;
     fs::write(path, &readme)?;
+
+    // the table is what matters, a missing chart shouldn't fail the update.
+    if let Err(e) = benchmark_chart::write(&timings) {
+        eprintln!("{ANSI_BOLD}Warning:{ANSI_RESET} could not write {CHART_PATH}: {e}");
+    }
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{is_row_stale, update_content, MARKER};
+    use crate::template::timings::{mock_timings, Timing};
+    use crate::template::{Day, SEASON_LENGTH};
+    use crate::{day, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
+        mock_timings(&[
+            (day!(1), Some("10ms"), Some("20ms")),
+            (day!(2), Some("30ms"), Some("40ms")),
+            (day!(4), Some("40ms"), Some("50ms")),
+        ])
     }
 
     #[test]
@@ -167,11 +187,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "![Benchmarks](./.assets/benchmarks.svg)",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +202,34 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_single_part_days() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![Timing::new(
+                Day::new(SEASON_LENGTH).unwrap(),
+                "1ms".parse().ok(),
+                None,
+            )],
+        };
+        update_content(&mut s, timings, 1.0).unwrap();
+        let last_day = Day::new(SEASON_LENGTH).unwrap();
+        assert!(s.contains(&format!(
+            "| [Day {SEASON_LENGTH}](./src/bin/{last_day}.rs) | `1.0ms` |  |"
+        )));
+    }
+
+    #[test]
+    fn detects_stale_rows() {
+        let mut s = format!("foo\n{}{}", MARKER, MARKER);
+        let timings = get_mock_timings();
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+
+        assert!(!is_row_stale(&s, day!(1), Some(&timings.data[0])));
+        assert!(is_row_stale(&s, day!(1), Some(&timings.data[1])));
+        assert!(is_row_stale(&s, day!(3), Some(&timings.data[1])));
+        assert!(is_row_stale(&s, day!(4), None));
+        assert!(!is_row_stale(&s, day!(3), None));
+    }
 }
@@ -0,0 +1,191 @@
+/// Module that updates the readme with the stars earned so far.
+/// Replaces the `advent-readme-stars` workflow: the table is built from the answers recorded on
+/// accepted submissions, so it needs neither a session cookie nor network access.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{all_days, aoc_year, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+const STAR: &str = "⭐";
+
+fn construct_table(year: u16, answers: &Answers) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let mut days: Vec<Day> = answers.data.iter().map(|a| a.day).collect();
+    days.dedup();
+
+    for day in days {
+        let star = |part: u8| {
+            if has_star(answers, day, part) {
+                STAR
+            } else {
+                " "
+            }
+        };
+        lines.push(format!(
+            "| [Day {0}](https://adventofcode.com/{year}/day/{0}) | {1} | {2} |",
+            day.into_inner(),
+            star(1),
+            star(2),
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+/// The second star of the last day is given for free once every other star was earned.
+fn has_star(answers: &Answers, day: Day, part: u8) -> bool {
+    if part == 2 && day.part_count() == 1 {
+        return answers.is_solved(day, 1)
+            && all_days()
+                .filter(|d| *d != day)
+                .all(|d| answers.is_solved(d, 1) && answers.is_solved(d, 2));
+    }
+    answers.is_solved(day, part)
+}
+
+/// Reads the stars of an existing table, e.g. one written by the `advent-readme-stars` workflow.
+/// The answers themselves are unknown and left empty.
+fn parse_table(readme: &str) -> Answers {
+    let mut answers = Answers::default();
+    let Ok(pos) = locate_table(readme, MARKER) else {
+        return answers;
+    };
+
+    for line in readme[pos.pos_start..pos.pos_end].lines() {
+        let Some(rest) = line.strip_prefix("| [Day ") else {
+            continue;
+        };
+        let Some(day) = rest
+            .split(']')
+            .next()
+            .and_then(|n| n.parse::<u8>().ok())
+            .and_then(Day::new)
+        else {
+            continue;
+        };
+        let cells: Vec<&str> = line.split('|').collect();
+        for part in 1..=2 {
+            if cells
+                .get(usize::from(part) + 1)
+                .is_some_and(|c| c.contains(STAR))
+            {
+                answers.record(day, part, None);
+            }
+        }
+    }
+    answers
+}
+
+fn update_content(s: &mut String, year: u16, answers: &Answers) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, answers);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Records an accepted answer and rebuilds the progress table of the README.
+/// Without recorded answers yet, the stars already in the README are imported first.
+/// If the recorded answers can't be read, neither they nor the README are touched.
+pub fn record(day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    let mut answers = if Answers::file_exists() {
+        Answers::read_from_file().map_err(Error::Parser)?
+    } else {
+        parse_table(&readme)
+    };
+    answers.record(day, part, Some(answer.into()));
+    answers.store_file()?;
+
+    let year = aoc_year().ok_or_else(|| Error::Parser("AOC_YEAR is not set.".into()))?;
+    update_content(&mut readme, year, &answers)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_table, update_content, MARKER};
+    use crate::day;
+    use crate::template::all_days;
+    use crate::template::answers::Answers;
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, Some("11".into()));
+        answers.record(day!(1), 2, Some("31".into()));
+        answers.record(day!(3), 1, None);
+        answers
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2024, &get_mock_answers()).unwrap();
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, 2024, &get_mock_answers()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn imports_existing_stars() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let answers = get_mock_answers();
+        update_content(&mut s, 2024, &answers).unwrap();
+
+        let imported = parse_table(&s);
+        assert_eq!(imported.data.len(), 3);
+        assert!(imported.is_solved(day!(1), 2));
+        assert!(imported.is_solved(day!(3), 1));
+        assert!(imported.data.iter().all(|a| a.answer.is_none()));
+        assert!(parse_table("# readme").data.is_empty());
+    }
+
+    #[test]
+    fn gives_last_star_when_complete() {
+        let mut answers = Answers::default();
+        for day in all_days() {
+            answers.record(day, 1, None);
+            if day.part_count() == 2 {
+                answers.record(day, 2, None);
+            }
+        }
+        let last = all_days().last().unwrap().into_inner();
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, 2024, &answers).unwrap();
+        assert!(s.contains(&format!(
+            "| [Day {last}](https://adventofcode.com/2024/day/{last}) | ⭐ | ⭐ |"
+        )));
+    }
+}
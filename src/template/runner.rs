@@ -1,33 +1,525 @@
+use std::cell::Cell;
 /// Encapsulates code that interacts with solution functions.
+use std::collections::hash_map::RandomState;
 use std::fmt::Display;
+use std::hash::BuildHasher;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{stdin, stdout, Read, Write};
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Output;
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, fs, panic, process};
 
+use crate::property::shrink;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, bench_conditions, cli, flamegraph, profiler, read_file, readme_progress, Day,
+    ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{collections, property};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const PROFILES_DIR: &str = "data/profiles";
+
+/// How long a part is run repeatedly for when profiling.
+const PROFILE_DURATION: Duration = Duration::from_secs(3);
+
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/inputs/{day}.txt`, the default.
+    Puzzle,
+    /// `data/examples/{day}.txt`, with `--example`.
+    Example,
+    /// Any file, with `--input <path>`.
+    File(PathBuf),
+    /// Standard input, with `--input -`.
+    Stdin,
+}
+
+/// What `cargo shrink` compares a part with: a shrunk input must keep disagreeing with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Oracle {
+    /// A variant of the part trusted to be correct, with `--shrink <variant>`.
+    Variant(String),
+    /// The answer the part should give, with `--shrink-expect <answer>`.
+    Answer(String),
+}
+
+/// Command-line options of a solution binary, forwarded by `cargo solve` and `cargo time`.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub part: Option<u8>,
+    pub input: InputSource,
+    pub time: bool,
+    pub submit: Option<u8>,
+    /// Core to pin the process to before running, with `--pin-cpu <n>`.
+    pub pin_cpu: Option<usize>,
+    /// Profile the parts instead of running them once, with `--flamegraph`.
+    pub flamegraph: bool,
+    /// Run the parts this many times and fail if the answers differ, with `--check-determinism <n>`.
+    pub check_determinism: Option<u32>,
+    /// Reseed the fast hash maps before every run of the determinism check, with `--random-seed`.
+    pub random_seed: bool,
+    /// Run and benchmark every implementation of the parts, with `--variants`.
+    pub variants: bool,
+    /// Minimize the input while the part disagrees with this oracle.
+    pub shrink: Option<Oracle>,
+}
+
+impl RunOptions {
+    /// Parses the options of the running binary, exiting on invalid arguments.
+    /// `parts` lists the parts compiled into the binary. Pins the process if `--pin-cpu` is set.
+    pub fn from_env(parts: &[u8]) -> Self {
+        let args = pico_args::Arguments::from_env();
+        let options = Self::parse(args, parts).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        });
+
+        if let Some(cpu) = options.pin_cpu {
+            if let Err(e) = bench_conditions::pin_to_cpu(cpu) {
+                eprintln!("Error: could not pin to CPU {cpu}: {e}");
+                process::exit(1);
+            }
+        }
+        options
+    }
+
+    fn parse(mut args: pico_args::Arguments, parts: &[u8]) -> Result<Self, String> {
+        let time = args.contains("--time");
+        let example = args.contains("--example");
+        let flamegraph = args.contains("--flamegraph");
+        let random_seed = args.contains("--random-seed");
+        let variants = args.contains("--variants");
+        let part = args
+            .opt_value_from_fn("--part", cli::parse_part)
+            .map_err(|e| e.to_string())?;
+        let submit = args
+            .opt_value_from_fn("--submit", cli::parse_part)
+            .map_err(|e| e.to_string())?;
+        let path: Option<String> = args
+            .opt_value_from_str("--input")
+            .map_err(|e| e.to_string())?;
+        let pin_cpu = args
+            .opt_value_from_str("--pin-cpu")
+            .map_err(|e| e.to_string())?;
+        let shrink_variant: Option<String> = args
+            .opt_value_from_str("--shrink")
+            .map_err(|e| e.to_string())?;
+        let shrink_answer: Option<String> = args
+            .opt_value_from_str("--shrink-expect")
+            .map_err(|e| e.to_string())?;
+        let check_determinism: Option<u32> = args
+            .opt_value_from_str("--check-determinism")
+            .map_err(|e| e.to_string())?;
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(format!("unexpected argument(s): {remaining:?}."));
+        }
+
+        let input = match (path, example) {
+            (Some(_), true) => return Err("`--input` and `--example` are exclusive.".into()),
+            (Some(path), false) if path == "-" => InputSource::Stdin,
+            (Some(path), false) => InputSource::File(path.into()),
+            (None, true) => InputSource::Example,
+            (None, false) => InputSource::Puzzle,
+        };
+
+        if let Some(part) = part.filter(|p| !parts.contains(p)) {
+            return Err(format!("part {part} is not enabled for this solution."));
+        }
+
+        let shrink = match (shrink_variant, shrink_answer) {
+            (Some(_), Some(_)) => {
+                return Err("`--shrink` and `--shrink-expect` are exclusive.".into())
+            }
+            (Some(variant), None) => Some(Oracle::Variant(variant)),
+            (None, Some(answer)) => Some(Oracle::Answer(answer)),
+            (None, None) => None,
+        };
+
+        match check_determinism {
+            Some(runs) if runs < 2 => {
+                return Err("`--check-determinism` needs at least 2 runs.".into())
+            }
+            None if random_seed => {
+                return Err("`--random-seed` requires `--check-determinism`.".into())
+            }
+            _ => {}
+        }
+
+        let modes = [
+            flamegraph,
+            check_determinism.is_some(),
+            variants,
+            shrink.is_some(),
+        ];
+        if modes.iter().filter(|m| **m).count() + usize::from(submit.is_some()) > 1 {
+            return Err(
+                "`--flamegraph`, `--check-determinism`, `--variants`, `--shrink` and `--submit` are exclusive."
+                    .into(),
+            );
+        }
+
+        if shrink.is_some() && part.is_none() {
+            return Err("`--shrink` and `--shrink-expect` require `--part`.".into());
+        }
+
+        Ok(Self {
+            part,
+            input,
+            time,
+            submit,
+            pin_cpu,
+            flamegraph,
+            check_determinism,
+            random_seed,
+            variants,
+            shrink,
+        })
+    }
+
+    /// Whether `part` was selected, i.e. `--part` is absent or matches.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Reads the selected input, exiting if it can't be read.
+    pub fn read_input(&self, day: Day) -> String {
+        let input = match &self.input {
+            InputSource::Puzzle => return read_file("inputs", day),
+            InputSource::Example => return read_file("examples", day),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut buf = String::new();
+                stdin().read_to_string(&mut buf).map(|_| buf)
+            }
+        };
+        input.unwrap_or_else(|e| {
+            eprintln!("Error: could not read input: {e}");
+            process::exit(1);
+        })
+    }
+}
+
+/// A named implementation of a solution part, see the `variants` of [`crate::solution`].
+pub struct Variant<T> {
+    pub name: &'static str,
+    pub func: fn(&str) -> Option<T>,
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if options.flamegraph {
+        profile_part(func, input, day, part);
+        return;
+    }
+
+    if let Some(runs) = options.check_determinism {
+        check_determinism(func, input, &part_str, runs, options.random_seed);
+        return;
+    }
+
+    let (result, duration, samples) = run_timed(func, input, options.time, |result| {
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if options.submit == Some(part) {
+            match submit_result(&result, day, part) {
+                Ok(output) if aoc_cli::is_accepted(&output) => {
+                    if let Err(e) = readme_progress::record(day, part, &result.to_string()) {
+                        eprintln!("failed to update progress in README: {e:?}");
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("failed to submit result: {e}"),
+            }
+        }
+    }
+}
+
+/// Runs a solution part repeatedly under the sampling profiler and writes a flame graph
+/// to `data/profiles/{day}-part{part}.svg`.
+fn profile_part<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day, part: u8) {
+    print!("Part {part}: {ANSI_ITALIC}profiling{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let profile = profiler::profile(
+        || {
+            black_box(func(black_box(input.clone())));
+        },
+        PROFILE_DURATION,
+    );
+
+    let written = profile.and_then(|profile| {
+        let path = format!("{PROFILES_DIR}/{day}-part{part}.svg");
+        let title = format!("Day {day} part {part}");
+        fs::create_dir_all(PROFILES_DIR)?;
+        fs::write(&path, flamegraph::render(&profile, &title))?;
+        Ok((path, profile.samples()))
+    });
+
+    match written {
+        Ok((path, samples)) => println!("\rPart {part}: wrote {samples} samples to {path}"),
+        Err(e) => {
+            println!();
+            eprintln!("Error: could not profile part {part}: {e}");
+            process::exit(1);
+        }
     }
 }
 
+/// Runs and benchmarks every variant of a solution part, comparing their speed to the first one.
+/// Exits with an error if their answers differ.
+pub fn run_variants<T: Display>(variants: &[Variant<T>], input: &str, part: u8) {
+    let mut answers = vec![];
+    let mut durations: Vec<Duration> = vec![];
+
+    for variant in variants {
+        let label = format!("Part {part} [{}]", variant.name);
+        let (result, duration, samples) = run_timed(variant.func, input, true, |result| {
+            print_result(result, &label, "");
+        });
+
+        let mut duration_str = format_duration(&duration, samples);
+        if let Some(first) = durations.first() {
+            let ratio = duration.as_secs_f64() / first.as_secs_f64().max(f64::EPSILON);
+            duration_str.insert_str(
+                duration_str.len() - 1,
+                &format!(", {ratio:.2}x {}", variants[0].name),
+            );
+        }
+        print_result(&result, &label, &duration_str);
+
+        answers.push(result.map(|answer| answer.to_string()));
+        durations.push(duration);
+    }
+
+    if let Some(i) = first_mismatch(&answers) {
+        eprintln!(
+            "Error: the variants of part {part} disagree: `{}` answered {}, `{}` answered {}.",
+            variants[0].name,
+            describe_answer(&answers[0]),
+            variants[i].name,
+            describe_answer(&answers[i])
+        );
+        process::exit(1);
+    }
+}
+
+thread_local! {
+    static QUIET_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Silences the panic messages of the current thread until dropped, even if it unwinds.
+struct QuietPanics;
+
+impl QuietPanics {
+    fn new() -> Self {
+        // the hook is process-wide, install one that defers to the previous hook once and only
+        // toggle a flag of this thread, which is safe to reset while unwinding.
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if !QUIET_PANICS.get() {
+                    previous(info);
+                }
+            }));
+        });
+        QUIET_PANICS.set(true);
+        Self
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET_PANICS.set(false);
+    }
+}
+
+/// Whether `solution` and `expected` both answer `input`, differently. Inputs either of them
+/// panics on or has no answer for are most likely malformed, so they are not interesting.
+fn disagree(solution: Option<String>, expected: Option<String>) -> bool {
+    matches!((solution, expected), (Some(actual), Some(expected)) if actual != expected)
+}
+
+/// Minimizes the input of a part while its first variant disagrees with `oracle` on it, and
+/// writes the result to `data/examples/{day}-{part}.txt`, where `read_file_part` finds it.
+pub fn shrink_part<T: Display>(
+    variants: &[Variant<T>],
+    input: &str,
+    day: Day,
+    part: u8,
+    oracle: &Oracle,
+) {
+    let solution = &variants[0];
+    let (reference, answer) = match oracle {
+        Oracle::Variant(name) => {
+            let Some(reference) = variants[1..].iter().find(|v| v.name == name) else {
+                let names: Vec<&str> = variants[1..].iter().map(|v| v.name).collect();
+                eprintln!(
+                    "Error: `{name}` is not a variant of part {part}, expected one of: {}.",
+                    if names.is_empty() {
+                        "(none, see `solution!`)".into()
+                    } else {
+                        names.join(", ")
+                    }
+                );
+                process::exit(1);
+            };
+            (Some(reference), None)
+        }
+        Oracle::Answer(answer) => (None, Some(answer)),
+    };
+    let expected = |input: &str| match reference {
+        Some(reference) => property::answer(reference, input),
+        None => answer.cloned(),
+    };
+    let describe = |input: &str| match reference {
+        Some(reference) => format!(
+            "`{}` answers {}, `{}` answers {}.",
+            solution.name,
+            property::outcome(solution, input),
+            reference.name,
+            property::outcome(reference, input)
+        ),
+        None => format!(
+            "`{}` answers {}, expected `{}`.",
+            solution.name,
+            property::outcome(solution, input),
+            answer.map_or("", String::as_str)
+        ),
+    };
+
+    let quiet = QuietPanics::new();
+    let is_failing = |input: &str| disagree(property::answer(solution, input), expected(input));
+
+    if !is_failing(input) {
+        drop(quiet);
+        eprintln!(
+            "Error: {} Shrinking needs two different answers.",
+            describe(input)
+        );
+        process::exit(1);
+    }
+
+    print!(
+        "Part {part}: {ANSI_ITALIC}shrinking {} lines{ANSI_RESET}",
+        input.lines().count()
+    );
+    let _ = stdout().flush();
+    let reduced = shrink::minimize(input, is_failing);
+    drop(quiet);
+    println!(
+        "\rPart {part}: shrunk {} lines to {}",
+        input.lines().count(),
+        reduced.lines().count()
+    );
+    println!("{}", describe(&reduced));
+
+    let path = format!("data/examples/{day}-{part}.txt");
+    if Path::new(&path).exists() {
+        println!("{reduced}");
+        eprintln!("Error: {path} already exists, printed the input instead.");
+        process::exit(1);
+    }
+    if let Err(e) = fs::write(&path, &reduced) {
+        eprintln!("Error: could not write {path}: {e}");
+        process::exit(1);
+    }
+    println!("Wrote {path}");
+}
+
+/// Runs every variant of a solution part once and panics if their answers differ.
+/// Called by the test `solution!` generates for parts with variants.
+pub fn assert_variants_agree<T: Display>(variants: &[Variant<T>], input: &str) {
+    let answers: Vec<Option<String>> = variants
+        .iter()
+        .map(|variant| (variant.func)(input).map(|answer| answer.to_string()))
+        .collect();
+
+    if let Some(i) = first_mismatch(&answers) {
+        panic!(
+            "`{}` answered {}, but `{}` answered {}",
+            variants[0].name,
+            describe_answer(&answers[0]),
+            variants[i].name,
+            describe_answer(&answers[i])
+        );
+    }
+}
+
+/// Runs a solution part `runs` times and exits with an error if the answers differ, e.g. because
+/// the solution depends on the iteration order of a hash map. With `random_seed`, the fast hash
+/// maps are reseeded before every run to shuffle that order.
+fn check_determinism<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    part: &str,
+    runs: u32,
+    random_seed: bool,
+) {
+    print!("{part}: {ANSI_ITALIC}checking determinism{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let seeds = RandomState::new();
+    let answers: Vec<Option<String>> = (0..runs)
+        .map(|run| {
+            if random_seed {
+                #[allow(clippy::cast_possible_truncation)]
+                collections::set_hash_seed(seeds.hash_one(run) as usize);
+            }
+            func(input.clone()).map(|answer| answer.to_string())
+        })
+        .collect();
+
+    match first_mismatch(&answers) {
+        None => print_result(&answers[0], part, &format!(" (same answer in {runs} runs)")),
+        Some(run) => {
+            println!();
+            eprintln!(
+                "Error: {part} is not deterministic: run 1 answered {}, run {} answered {}.",
+                describe_answer(&answers[0]),
+                run + 1,
+                describe_answer(&answers[run])
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Index of the first answer that differs from the first one.
+fn first_mismatch(answers: &[Option<String>]) -> Option<usize> {
+    answers.iter().position(|answer| *answer != answers[0])
+}
+
+fn describe_answer(answer: &Option<String>) -> String {
+    answer
+        .as_deref()
+        .map_or_else(|| "nothing".into(), |answer| format!("`{answer}`"))
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -43,7 +535,7 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -129,41 +621,172 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if aoc-cli is installed.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+) -> Result<Output, aoc_cli::AocCommandError> {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    println!("Submitting result via aoc-cli...");
+    aoc_cli::submit(day, part, &result.to_string())
+}
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        assert_variants_agree, disagree, first_mismatch, InputSource, Oracle, QuietPanics,
+        RunOptions, Variant, QUIET_PANICS,
+    };
+    use std::ffi::OsString;
+    use std::panic;
+
+    fn parse(args: &[&str], parts: &[u8]) -> Result<RunOptions, String> {
+        let args = args.iter().map(OsString::from).collect();
+        RunOptions::parse(pico_args::Arguments::from_vec(args), parts)
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    #[test]
+    fn parses_defaults() {
+        let options = parse(&[], &[1, 2]).unwrap();
+        assert_eq!(options.input, InputSource::Puzzle);
+        assert!(options.runs_part(1) && options.runs_part(2));
+        assert!(!options.time);
+        assert_eq!(options.pin_cpu, None);
+        assert!(!options.flamegraph);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    #[test]
+    fn parses_part_and_input() {
+        let options = parse(&["--part", "2", "--input", "foo.txt", "--time"], &[1, 2]).unwrap();
+        assert!(!options.runs_part(1) && options.runs_part(2));
+        assert_eq!(options.input, InputSource::File("foo.txt".into()));
+        assert!(options.time);
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+        let options = parse(&["--input", "-", "--pin-cpu", "3"], &[1, 2]).unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+        assert_eq!(options.pin_cpu, Some(3));
+
+        let options = parse(&["--example", "--submit", "1"], &[1, 2]).unwrap();
+        assert_eq!(options.input, InputSource::Example);
+        assert_eq!(options.submit, Some(1));
+
+        let options = parse(&["--check-determinism", "5", "--random-seed"], &[1, 2]).unwrap();
+        assert_eq!(options.check_determinism, Some(5));
+        assert!(options.random_seed);
+
+        let options = parse(&["--variants", "--part", "1"], &[1, 2]).unwrap();
+        assert!(options.variants);
 
-    if part_submit != part {
-        return None;
+        let options = parse(&["--shrink", "part_two_naive", "--part", "2"], &[1, 2]).unwrap();
+        assert_eq!(
+            options.shrink,
+            Some(Oracle::Variant("part_two_naive".into()))
+        );
+
+        let options = parse(&["--shrink-expect", "6,1", "--part", "2"], &[1, 2]).unwrap();
+        assert_eq!(options.shrink, Some(Oracle::Answer("6,1".into())));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(parse(&["--part", "3"], &[1, 2]).is_err());
+        assert!(parse(&["--part", "2"], &[1]).is_err());
+        assert!(parse(&["--example", "--input", "foo.txt"], &[1, 2]).is_err());
+        assert!(parse(&["--foo"], &[1, 2]).is_err());
+        assert!(parse(&["--pin-cpu", "first"], &[1, 2]).is_err());
+        assert!(parse(&["--check-determinism", "1"], &[1, 2]).is_err());
+        assert!(parse(&["--random-seed"], &[1, 2]).is_err());
+        assert!(parse(&["--variants", "--flamegraph"], &[1, 2]).is_err());
+        assert!(parse(&["--shrink", "part_two_naive"], &[1, 2]).is_err());
+        assert!(parse(
+            &["--shrink", "naive", "--shrink-expect", "42", "--part", "2"],
+            &[1, 2]
+        )
+        .is_err());
+        assert!(parse(
+            &["--shrink", "naive", "--part", "2", "--submit", "2"],
+            &[1, 2]
+        )
+        .is_err());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn only_different_answers_disagree() {
+        let answer = |s: &str| Some(s.to_string());
+        assert!(disagree(answer("1"), answer("2")));
+        assert!(!disagree(answer("1"), answer("1")));
+        assert!(!disagree(None, answer("1")));
+        assert!(!disagree(answer("1"), None));
+        assert!(!disagree(None, None));
+    }
+
+    #[test]
+    fn quiet_panics_end_when_unwinding() {
+        let result = panic::catch_unwind(|| {
+            let _quiet = QuietPanics::new();
+            assert!(QUIET_PANICS.get());
+            panic!("not printed");
+        });
+        assert!(result.is_err());
+        assert!(!QUIET_PANICS.get());
+    }
+
+    fn count_lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    fn count_newlines(input: &str) -> Option<usize> {
+        Some(input.matches('\n').count())
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let variants = [
+            Variant {
+                name: "lines",
+                func: count_lines,
+            },
+            Variant {
+                name: "newlines",
+                func: count_newlines,
+            },
+        ];
+        assert_variants_agree(&variants, "a\nb\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "`lines` answered `2`, but `newlines` answered `1`")]
+    fn rejects_disagreeing_variants() {
+        let variants = [
+            Variant {
+                name: "lines",
+                func: count_lines,
+            },
+            Variant {
+                name: "newlines",
+                func: count_newlines,
+            },
+        ];
+        assert_variants_agree(&variants, "a\nb");
+    }
+
+    #[test]
+    fn finds_differing_answers() {
+        let answers = |a: &[Option<&str>]| -> Vec<Option<String>> {
+            a.iter().map(|a| a.map(String::from)).collect()
+        };
+        assert_eq!(first_mismatch(&answers(&[Some("1"), Some("1")])), None);
+        assert_eq!(
+            first_mismatch(&answers(&[Some("1"), Some("1"), Some("2")])),
+            Some(2)
+        );
+        assert_eq!(first_mismatch(&answers(&[Some("1"), None])), Some(1));
+    }
 }
@@ -1,17 +1,110 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap, fmt::Display, fs, io::Error, path::Path, process, str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::bench_conditions::BenchConditions;
+use crate::template::{fingerprint, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the `timings.json` format.
+/// 1: parts as formatted strings like `"74.13ns"`, no `version` key.
+/// 2: parts as `{ "nanos": .., "samples": .. }` objects.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Number of past runs kept per part for [`MergePolicy::Median`].
+pub const RECENT_RUNS: usize = 10;
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Timings of the last runs of each part, oldest first.
+    pub recent: [Vec<PartTiming>; 2],
+    /// Fingerprint of the sources and input the timing was measured with, see [`fingerprint::of`].
+    pub fingerprint: Option<String>,
+    /// Conditions the timing was measured under, see [`BenchConditions::detect`].
+    pub conditions: Option<BenchConditions>,
+}
+
+/// Benchmark result of a single part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Mean duration of a sample.
+    pub nanos: f64,
+    /// Number of samples taken, 0 if unknown (e.g. migrated from the first format version).
+    pub samples: u64,
+}
+
+impl PartTiming {
+    pub fn new(nanos: f64, samples: u64) -> Self {
+        Self { nanos, samples }
+    }
+}
+
+impl Display for PartTiming {
+    /// Formats like the runner does, e.g. `72.0µs`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(self.nanos.round() as u64);
+        write!(f, "{duration:.1?}")
+    }
+}
+
+impl FromStr for PartTiming {
+    type Err = String;
+
+    /// Parses a timing as printed by the runner, e.g. `72.0µs` or `72.0µs @ 100 samples`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (duration, samples) = match s.split_once('@') {
+            Some((duration, samples)) => {
+                let samples = samples.trim().trim_end_matches("samples").trim();
+                (duration.trim(), samples.parse().map_err(|_| s.to_string())?)
+            }
+            None => (s.trim(), 0),
+        };
+        let nanos = parse_duration(duration).ok_or_else(|| format!("invalid timing `{s}`"))?;
+        Ok(Self { nanos, samples })
+    }
+}
+
+/// How [`Timings::merge`] picks the timing of a part that was measured again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Keep the new timing.
+    #[default]
+    Latest,
+    /// Keep the fastest of the stored and the new timing.
+    Best,
+    /// Keep the median of the last `N` timings.
+    Median(usize),
+}
+
+impl FromStr for MergePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "latest" => return Ok(MergePolicy::Latest),
+            "best" => return Ok(MergePolicy::Best),
+            _ => {}
+        }
+
+        s.strip_prefix("median:")
+            .and_then(|n| n.parse().ok())
+            .filter(|n| (1..=RECENT_RUNS).contains(n))
+            .map(MergePolicy::Median)
+            .ok_or_else(|| {
+                format!(
+                    "invalid policy `{s}`, expecting `latest`, `best` or `median:N` with N up to {RECENT_RUNS}"
+                )
+            })
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -30,26 +123,45 @@ impl Timings {
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Files stored with an older format version are migrated on the fly.
+    ///
+    /// Any other error is returned, so that callers don't overwrite a file they couldn't read.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = Path::new(TIMINGS_FILE_PATH);
+        if !path.exists() {
+            return Ok(Timings::default());
         }
+        Timings::read_from_path(path).map_err(|e| format!("{TIMINGS_FILE_PATH}: {e}"))
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// Same as [`Timings::read_from_file`], for commands that can't go on without the stored
+    /// timings: prints the error and exits.
+    pub fn read_from_file_or_exit() -> Self {
+        Timings::read_from_file().unwrap_or_else(|e| {
+            eprintln!("Error: could not read the stored timings, {e}");
+            process::exit(1);
+        })
+    }
+
+    /// Rehydrate timings from any JSON file, e.g. one exported by someone else.
+    pub fn read_from_path(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {e}", path.display()))
+            .and_then(Timings::try_from)
+    }
+
+    /// Merge two sets of timings part by part, picking between stored and new timings with `policy`.
+    /// Parts missing from the new run, e.g. because it failed, keep their stored timing, unless the
+    /// new run was measured with other sources or input: stored timings of the day are dropped then.
+    pub fn merge(&self, new: &Self, policy: MergePolicy) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let merged = match self.data.iter().find(|t| t.day == timing.day) {
+                Some(stored) => stored.merge(timing, policy),
+                None => Timing::new(timing.day, None, None).merge(timing, policy),
+            };
+            data.push(merged);
         }
 
         for timing in &self.data {
@@ -58,7 +170,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -68,9 +180,118 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
+        self.data.iter().any(|t| t.day == day && t.is_complete())
+    }
+
+    /// Whether the stored timing of `day` was measured with other sources or input than the current ones.
+    /// Timings without a fingerprint are stale, days without a timing are not.
+    pub fn is_day_stale(&self, day: Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .find(|t| t.day == day)
+            .is_some_and(|t| t.fingerprint.is_none() || t.fingerprint != fingerprint::of(day))
+    }
+}
+
+impl Timing {
+    /// A timing of `day` from the timings of its parts, without recent runs, fingerprint or conditions.
+    pub fn new(day: Day, part_1: Option<PartTiming>, part_2: Option<PartTiming>) -> Self {
+        Timing {
+            day,
+            part_1,
+            part_2,
+            total_nanos: [part_1, part_2].iter().flatten().map(|p| p.nanos).sum(),
+            recent: Default::default(),
+            fingerprint: None,
+            conditions: None,
+        }
+    }
+
+    /// Whether every part of the day has a timing. The last day only has one part.
+    pub fn is_complete(&self) -> bool {
+        self.part_1.is_some() && (self.part_2.is_some() || self.day.part_count() == 1)
+    }
+
+    fn merge(&self, new: &Timing, policy: MergePolicy) -> Timing {
+        // timings measured with other sources or input can't be compared with the new ones,
+        // `Best` or `Median` would otherwise keep them under the new fingerprint.
+        let stored = if self.fingerprint == new.fingerprint {
+            self
+        } else {
+            &Timing::new(self.day, None, None)
+        };
+
+        let mut recent = stored.recent.clone();
+        let part_1 = merge_part(stored.part_1, new.part_1, &mut recent[0], policy);
+        let part_2 = merge_part(stored.part_2, new.part_2, &mut recent[1], policy);
+
+        // parts kept from the stored timing keep the conditions they were measured under,
+        // only take the new ones if the new run replaced all of them.
+        let replaced_all = (stored.part_1.is_none() || new.part_1.is_some())
+            && (stored.part_2.is_none() || new.part_2.is_some());
+        let conditions = if replaced_all {
+            new.conditions.clone()
+        } else {
+            stored.conditions.clone()
+        };
+
+        Timing {
+            recent,
+            fingerprint: new.fingerprint.clone(),
+            conditions,
+            ..Timing::new(self.day, part_1, part_2)
+        }
+    }
+}
+
+fn merge_part(
+    stored: Option<PartTiming>,
+    new: Option<PartTiming>,
+    recent: &mut Vec<PartTiming>,
+    policy: MergePolicy,
+) -> Option<PartTiming> {
+    let Some(new) = new else {
+        return stored;
+    };
+
+    recent.push(new);
+    if recent.len() > RECENT_RUNS {
+        recent.remove(0);
+    }
+
+    let picked = match (policy, stored) {
+        (MergePolicy::Best, Some(stored)) if stored.nanos <= new.nanos => stored,
+        (MergePolicy::Median(n), _) => {
+            let mut last: Vec<PartTiming> = recent.iter().rev().take(n).copied().collect();
+            last.sort_by(|a, b| a.nanos.total_cmp(&b.nanos));
+            last[(last.len() - 1) / 2]
+        }
+        _ => new,
+    };
+    Some(picked)
+}
+
+/// Parses a duration as printed by the runner, e.g. `72.0µs`, into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+    match s {
+        s if s.contains("ns") => parse("ns"),
+        s if s.contains("µs") => parse("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse("ms").map(|x| x * 1_000_000_f64),
+        _ => parse("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Timings of `(day, part_1, part_2)`, with parts as printed by the runner, e.g. `10ms`.
+#[cfg(feature = "test_lib")]
+pub(crate) fn mock_timings(data: &[(Day, Option<&str>, Option<&str>)]) -> Timings {
+    let parse = |part: &Option<&str>| part.map(|p| p.parse().unwrap());
+    Timings {
+        data: data
+            .iter()
+            .map(|(day, part_1, part_2)| Timing::new(*day, parse(part_1), parse(part_2)))
+            .collect(),
     }
 }
 
@@ -80,6 +301,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(FORMAT_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,9 +320,25 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: older versions are migrated while parsing each timing, see `parse_part`.
+        let version = match json.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| *v as u32)
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+        if version > FORMAT_VERSION {
+            return Err(format!(
+                "timings were stored with format version {version}, this template only supports up to {FORMAT_VERSION}."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -121,25 +362,38 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value.part_1.map_or(JsonValue::Null, JsonValue::from),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value.part_2.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "fingerprint".into(),
+            value
+                .fingerprint
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
         );
 
+        map.insert(
+            "conditions".into(),
+            value
+                .conditions
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, recent) in ["recent_1", "recent_2"].into_iter().zip(&value.recent) {
+            map.insert(
+                key.into(),
+                JsonValue::Array(recent.iter().copied().map(JsonValue::from).collect()),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -158,76 +412,123 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part = |key: &str| -> Result<Option<PartTiming>, String> {
+            let value = json
+                .get(key)
+                .ok_or_else(|| format!("Expected timing.{key} to be present."))?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            parse_part(value)
+                .map(Some)
+                .ok_or_else(|| format!("Expected timing.{key} to be null or a part timing."))
+        };
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: recent runs were added later, files without them are still valid.
+        let recent = |key: &str| -> Result<Vec<PartTiming>, String> {
+            let Some(v) = json.get(key) else {
+                return Ok(vec![]);
+            };
+            v.get::<Vec<JsonValue>>()
+                .and_then(|runs| runs.iter().map(parse_part).collect())
+                .ok_or_else(|| format!("Expected timing.{key} to be an array of part timings."))
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            recent: [recent("recent_1")?, recent("recent_2")?],
+            fingerprint: json
+                .get("fingerprint")
+                .and_then(|v| v.get::<String>())
+                .cloned(),
+            conditions: match json.get("conditions") {
+                Some(v) if !v.is_null() => Some(BenchConditions::try_from(v)?),
+                _ => None,
+            },
         })
     }
 }
 
+impl From<PartTiming> for JsonValue {
+    fn from(value: PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        JsonValue::Object(map)
+    }
+}
+
+/// Parses a part timing, either an object or a formatted string from format version 1.
+fn parse_part(value: &JsonValue) -> Option<PartTiming> {
+    if let Some(s) = value.get::<String>() {
+        return s.parse().ok();
+    }
+
+    let map = value.get::<HashMap<String, JsonValue>>()?;
+    let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).copied();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(PartTiming {
+        nanos: number("nanos")?,
+        samples: number("samples").unwrap_or(0.0) as u64,
+    })
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{mock_timings, Timings};
 
     fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
-            ],
-        }
+        mock_timings(&[
+            (day!(1), Some("10ms"), Some("20ms")),
+            (day!(2), Some("30ms"), Some("40ms")),
+            (day!(4), Some("40ms"), None),
+        ])
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 300 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::new(1e+6, 300)));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "72.5µs", "total_nanos": 1072500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(PartTiming::new(1e+6, 0)));
+            assert_eq!(timing.part_2, Some(PartTiming::new(72_500_f64, 0)));
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
         }
 
         #[test]
@@ -254,9 +555,20 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[1].part_1, timings.data[1].part_1);
+            assert_eq!(parsed.data[1].part_1.unwrap().to_string(), "30.0ms");
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -279,55 +591,52 @@ mod tests {
         use crate::{
             day,
             template::timings::{Timing, Timings},
+            template::{Day, SEASON_LENGTH},
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![Timing::new(day!(1), "1ms".parse().ok(), "2ms".parse().ok())],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![Timing::new(day!(1), "1ms".parse().ok(), None)],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let last_day = Day::new(SEASON_LENGTH).unwrap();
+            let timings = Timings {
+                data: vec![Timing::new(last_day, "1ms".parse().ok(), None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(last_day), true);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1), None, None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{mock_timings, MergePolicy, Timing, Timings},
+            template::Day,
         };
 
         use super::get_mock_timings;
@@ -336,14 +645,9 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3), None, None)],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -356,27 +660,94 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2), None, None)],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1, "30ms".parse().ok());
+            assert_eq!(merged.data[1].total_nanos, 7e+7);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        fn run(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timings {
+            mock_timings(&[(day, part_1, part_2)])
+        }
+
+        #[test]
+        fn merges_parts_separately() {
+            let timings = get_mock_timings();
+            let merged = timings.merge(&run(day!(2), None, Some("45ms")), MergePolicy::Latest);
+            assert_eq!(merged.data[1].part_1, "30ms".parse().ok());
+            assert_eq!(merged.data[1].part_2, "45ms".parse().ok());
+            assert_eq!(merged.data[1].total_nanos, 7.5e+7);
+        }
+
+        #[test]
+        fn keeps_best_timings() {
+            let timings = get_mock_timings();
+            let merged = timings.merge(&run(day!(2), Some("35ms"), Some("2ms")), MergePolicy::Best);
+            assert_eq!(merged.data[1].part_1, "30ms".parse().ok());
+            assert_eq!(merged.data[1].part_2, "2ms".parse().ok());
+        }
+
+        #[test]
+        fn keeps_median_of_recent_timings() {
+            let policy = MergePolicy::Median(3);
+            let mut timings = Timings::default();
+            for time in ["10ms", "1s", "12ms", "11ms"] {
+                timings = timings.merge(&run(day!(1), Some(time), None), policy);
+            }
+            let recent: Vec<f64> = timings.data[0].recent[0].iter().map(|p| p.nanos).collect();
+            assert_eq!(recent, [1e+7, 1e+9, 1.2e+7, 1.1e+7]);
+            assert_eq!(timings.data[0].part_1, "12ms".parse().ok());
+            assert!(timings.data[0].recent[1].is_empty());
+        }
+
+        #[test]
+        fn drops_timings_of_other_sources() {
+            let fingerprinted = |timings: Timings, fingerprint: &str| Timings {
+                data: timings
+                    .data
+                    .into_iter()
+                    .map(|t| Timing {
+                        fingerprint: Some(fingerprint.into()),
+                        ..t
+                    })
+                    .collect(),
+            };
+            let mut stored = Timings::default();
+            for (part_1, part_2) in [("1ms", Some("2ms")), ("3ms", None)] {
+                let old = fingerprinted(run(day!(1), Some(part_1), part_2), "old");
+                stored = stored.merge(&old, MergePolicy::Best);
+            }
+            assert_eq!(stored.data[0].part_1, "1ms".parse().ok());
+            assert_eq!(stored.data[0].recent[0].len(), 2);
+
+            let new = fingerprinted(run(day!(1), Some("5ms"), None), "new");
+            let merged = stored.merge(&new, MergePolicy::Best);
+            assert_eq!(merged.data[0].part_1, "5ms".parse().ok());
+            assert_eq!(merged.data[0].part_2, None);
+            assert_eq!(merged.data[0].recent[0].len(), 1);
+            assert_eq!(merged.data[0].fingerprint.as_deref(), Some("new"));
+            assert!(!merged.is_day_complete(day!(1)));
+        }
+
+        #[test]
+        fn parses_policies() {
+            assert_eq!("best".parse(), Ok(MergePolicy::Best));
+            assert_eq!("median:5".parse(), Ok(MergePolicy::Median(5)));
+            assert!("median:0".parse::<MergePolicy>().is_err());
+            assert!("fastest".parse::<MergePolicy>().is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -384,7 +755,7 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 3);
         }
     }
@@ -0,0 +1,361 @@
+/// Exports stored timings to other formats and compares them to someone else's.
+use std::fmt::Write;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::build_profile::BuildProfile;
+use crate::template::readme_benchmarks::format_row;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Formats `cargo time --export` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// The `timings.json` format, so an export can be passed to `--compare-with`.
+    Json,
+    /// The table of the README.
+    Md,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "md" => Ok(ExportFormat::Md),
+            _ => Err(format!(
+                "unsupported format `{s}`, expecting `csv`, `json` or `md`"
+            )),
+        }
+    }
+}
+
+/// Renders `timings` in `format`.
+pub fn export(timings: &Timings, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => {
+            let mut s = String::from("day,part,nanos,samples\n");
+            for timing in &timings.data {
+                for (part, value) in parts(timing) {
+                    if let Some(value) = value {
+                        let _ = writeln!(
+                            s,
+                            "{},{part},{},{}",
+                            timing.day.into_inner(),
+                            value.nanos,
+                            value.samples
+                        );
+                    }
+                }
+            }
+            s
+        }
+        ExportFormat::Json => {
+            let mut s = JsonValue::from(timings.clone())
+                .format()
+                .unwrap_or_default();
+            s.push('\n');
+            s
+        }
+        ExportFormat::Md => {
+            let mut lines: Vec<String> = vec![
+                "| Day | Part 1 | Part 2 |".into(),
+                "| :---: | :---: | :---:  |".into(),
+            ];
+            lines.extend(timings.data.iter().map(format_row));
+            lines.push(String::new());
+            lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+            lines.join("\n") + "\n"
+        }
+    }
+}
+
+fn parts(timing: &Timing) -> [(u8, Option<PartTiming>); 2] {
+    [(1, timing.part_1), (2, timing.part_2)]
+}
+
+/// Renders `mine` and `theirs` side by side, one row per day and part.
+/// The ratio is `mine / theirs`, the total only sums parts both have measured.
+pub fn compare(mine: &Timings, theirs: &Timings) -> String {
+    let mut days: Vec<Day> = mine
+        .data
+        .iter()
+        .chain(&theirs.data)
+        .map(|t| t.day)
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let find = |timings: &Timings, day: Day, part: u8| -> Option<PartTiming> {
+        let timing = timings.data.iter().find(|t| t.day == day)?;
+        if part == 1 {
+            timing.part_1
+        } else {
+            timing.part_2
+        }
+    };
+    let cell = |value: Option<PartTiming>| value.map_or_else(|| "-".into(), |v| v.to_string());
+
+    let mut lines = vec![[
+        "Day".to_string(),
+        "Part".into(),
+        "Mine".into(),
+        "Theirs".into(),
+        "Ratio".into(),
+    ]];
+    let (mut total_mine, mut total_theirs) = (0.0, 0.0);
+
+    for day in days {
+        for part in 1..=2 {
+            let (a, b) = (find(mine, day, part), find(theirs, day, part));
+            if a.is_none() && b.is_none() {
+                continue;
+            }
+            let ratio = match (a, b) {
+                (Some(a), Some(b)) => {
+                    total_mine += a.nanos;
+                    total_theirs += b.nanos;
+                    format_ratio(a.nanos, b.nanos)
+                }
+                _ => "-".into(),
+            };
+            lines.push([day.to_string(), part.to_string(), cell(a), cell(b), ratio]);
+        }
+    }
+
+    let total = |nanos: f64| format!("{:.2}ms", nanos / 1_000_000_f64);
+    lines.push([
+        "Total".into(),
+        String::new(),
+        total(total_mine),
+        total(total_theirs),
+        format_ratio(total_mine, total_theirs),
+    ]);
+
+    let last = lines.len() - 1;
+    let mut s = render_table(&lines, &[0, last]);
+    let _ = writeln!(
+        s,
+        "\n{ANSI_ITALIC}Ratio is mine / theirs, below 1.00x means mine is faster.{ANSI_RESET}"
+    );
+    s
+}
+
+/// Renders the timings of the same days built with different profiles, one table per day.
+/// Ratios are relative to the first profile.
+pub fn compare_profiles(runs: &[(BuildProfile, Timings)]) -> String {
+    let Some((baseline, baseline_timings)) = runs.first() else {
+        return String::new();
+    };
+
+    let mut days: Vec<Day> = runs
+        .iter()
+        .flat_map(|(_, timings)| timings.data.iter().map(|t| t.day))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let header = [
+        "Profile".to_string(),
+        "Part 1".into(),
+        "Part 2".into(),
+        "Total".into(),
+        format!("vs {baseline}"),
+    ];
+    let cell = |value: Option<PartTiming>| value.map_or_else(|| "-".into(), |v| v.to_string());
+    let find = |timings: &Timings, day: Day| timings.data.iter().find(|t| t.day == day).cloned();
+
+    let mut s = String::new();
+    for &day in &days {
+        let base = find(baseline_timings, day).map(|t| t.total_nanos);
+        let mut lines = vec![header.clone()];
+        for (profile, timings) in runs {
+            let timing = find(timings, day);
+            let total = timing.as_ref().map(|t| t.total_nanos);
+            lines.push([
+                profile.to_string(),
+                cell(timing.as_ref().and_then(|t| t.part_1)),
+                cell(timing.as_ref().and_then(|t| t.part_2)),
+                total.map_or_else(|| "-".into(), |t| PartTiming::new(t, 0).to_string()),
+                match (total, base) {
+                    (Some(total), Some(base)) => format_ratio(total, base),
+                    _ => "-".into(),
+                },
+            ]);
+        }
+        let _ = writeln!(s, "{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        s.push_str(&render_table(&lines, &[]));
+        s.push('\n');
+    }
+
+    // only days every profile solved add up to comparable totals.
+    let common: Vec<Day> = days
+        .into_iter()
+        .filter(|day| runs.iter().all(|(_, t)| find(t, *day).is_some()))
+        .collect();
+    let totals: Vec<f64> = runs
+        .iter()
+        .map(|(_, timings)| {
+            common
+                .iter()
+                .filter_map(|day| find(timings, *day))
+                .map(|t| t.total_nanos)
+                .sum()
+        })
+        .collect();
+
+    let mut lines = vec![[
+        "Profile".to_string(),
+        "Total".into(),
+        format!("vs {baseline}"),
+    ]];
+    for ((profile, _), total) in runs.iter().zip(&totals) {
+        lines.push([
+            profile.to_string(),
+            PartTiming::new(*total, 0).to_string(),
+            format_ratio(*total, totals[0]),
+        ]);
+    }
+    let _ = writeln!(
+        s,
+        "{ANSI_BOLD}Total{ANSI_RESET} {ANSI_ITALIC}(days solved with every profile: {}){ANSI_RESET}",
+        common.len()
+    );
+    s.push_str(&render_table(&lines, &[]));
+    let _ = writeln!(
+        s,
+        "\n{ANSI_ITALIC}Below 1.00x means the profile is faster than {baseline}.{ANSI_RESET}"
+    );
+    s
+}
+
+/// Aligns the cells of `lines` in columns, printing the rows in `bold_rows` bold.
+fn render_table<const N: usize>(lines: &[[String; N]], bold_rows: &[usize]) -> String {
+    let widths: Vec<usize> = (0..N)
+        .map(|i| {
+            lines
+                .iter()
+                .map(|l| l[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut s = String::new();
+    for (row, line) in lines.iter().enumerate() {
+        let cells: Vec<String> = line
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{cell:<0$}", widths[i]))
+            .collect();
+        let line = cells.join("  ");
+        if bold_rows.contains(&row) {
+            let _ = writeln!(s, "{ANSI_BOLD}{}{ANSI_RESET}", line.trim_end());
+        } else {
+            let _ = writeln!(s, "{}", line.trim_end());
+        }
+    }
+    s
+}
+
+fn format_ratio(mine: f64, theirs: f64) -> String {
+    if theirs > 0.0 {
+        format!("{:.2}x", mine / theirs)
+    } else {
+        "-".into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, compare_profiles, export, ExportFormat};
+    use crate::template::build_profile::BuildProfile;
+    use crate::template::timings::{mock_timings, Timings};
+    use crate::template::Day;
+
+    fn timings(data: &[(u8, Option<&str>, Option<&str>)]) -> Timings {
+        let data: Vec<_> = data
+            .iter()
+            .map(|(day, part_1, part_2)| (Day::new(*day).unwrap(), *part_1, *part_2))
+            .collect();
+        mock_timings(&data)
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("md".parse::<ExportFormat>(), Ok(ExportFormat::Md));
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = export(
+            &timings(&[(1, Some("10ms @ 20 samples"), Some("1µs"))]),
+            ExportFormat::Csv,
+        );
+        assert_eq!(csv, "day,part,nanos,samples\n1,1,10000000,20\n1,2,1000,0\n");
+    }
+
+    #[test]
+    fn exports_json_readable_by_timings() {
+        let original = timings(&[(1, Some("10ms"), None), (4, Some("2ms"), Some("3ms"))]);
+        let json = export(&original, ExportFormat::Json);
+        let parsed = Timings::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), 2);
+        assert_eq!(parsed.data[1].part_2, original.data[1].part_2);
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let md = export(
+            &timings(&[(1, Some("10ms"), Some("20ms"))]),
+            ExportFormat::Md,
+        );
+        assert!(md.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |"));
+        assert!(md.ends_with("**Total: 30.00ms**\n"));
+    }
+
+    #[test]
+    fn compares_timings() {
+        let mine = timings(&[(1, Some("10ms"), Some("20ms")), (2, Some("5ms"), None)]);
+        let theirs = timings(&[(1, Some("5ms"), Some("40ms")), (3, Some("1ms"), None)]);
+        let table = compare(&mine, &theirs);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines[1].starts_with("01     1     10.0ms   5.0ms    2.00x"));
+        assert!(lines[2].starts_with("01     2     20.0ms   40.0ms   0.50x"));
+        assert!(lines[3].starts_with("02     1     5.0ms    -        -"));
+        assert!(lines[4].starts_with("03     1     -        1.0ms    -"));
+        assert!(lines[5].contains("30.00ms  45.00ms  0.67x"));
+    }
+
+    #[test]
+    fn compares_profiles() {
+        let runs = [
+            (
+                BuildProfile::release(),
+                timings(&[(1, Some("10ms"), Some("10ms")), (2, Some("1ms"), None)]),
+            ),
+            (
+                "lto".parse().unwrap(),
+                timings(&[(1, Some("5ms"), Some("10ms"))]),
+            ),
+        ];
+        let report = compare_profiles(&runs);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert!(lines[0].contains("Day 01"));
+        assert!(lines[1].contains("vs release"));
+        assert!(lines[2].starts_with("release  10.0ms  10.0ms  20.0ms  1.00x"));
+        assert!(lines[3].starts_with("lto      5.0ms   10.0ms  15.0ms  0.75x"));
+        assert!(lines[8].starts_with("lto      -"));
+        assert!(report.contains("(days solved with every profile: 1)"));
+        assert!(report.contains("lto      15.0ms  0.75x"));
+    }
+}